@@ -2,6 +2,8 @@ use ni_syscfg_sys::*;
 use paste::paste;
 use thiserror::Error;
 
+use crate::resources::FirmwareStatus;
+
 #[derive(Error, Debug)]
 pub enum NiSystemConfigurationError {
     #[error("API Error: {0:?}")]
@@ -12,6 +14,16 @@ pub enum NiSystemConfigurationError {
     NulStringError(#[from] std::ffi::NulError),
     #[error("Unexpected Enum Value from API: {0}")]
     UnexpectedEnumValue(i32),
+    #[error("Null in secret value")]
+    NulInSecret,
+    #[error("The resource does not support writing calibration data")]
+    CalibrationWriteNotSupported,
+    #[error("Simulated device {0} found but the session is set to reject simulated devices")]
+    SimulatedDeviceRejected(String),
+    #[error("Timed out waiting for the firmware operation to finish, last status was {0:?}")]
+    FirmwareTimeout(FirmwareStatus),
+    #[error("IO Error: {0}")]
+    IoError(#[from] std::io::Error),
 }
 
 macro_rules! syscfg_error {
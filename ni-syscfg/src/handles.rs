@@ -4,13 +4,22 @@
 //! as all handles need a drop and should not be clone/copy
 //! and we could enfore that with a new type here.
 
-use std::ffi::c_void;
+use std::ffi::{c_char, c_void};
 
 use crate::error::{api_status, NiSysCfgApiStatus, Result};
-use ni_syscfg_sys::NISysCfgCloseHandle;
+use ni_syscfg_sys::{NISysCfgCloseHandle, NISysCfgFreeDetailedString};
 
 pub type AnyHandle = *mut c_void;
 
 pub fn close_handle(handle: AnyHandle) -> Result<NiSysCfgApiStatus> {
     unsafe { api_status(NISysCfgCloseHandle(handle)) }
 }
+
+/// Free a detailed result string returned by the API, if one was provided.
+pub fn free_detailed_string(detailed: *mut c_char) {
+    if !detailed.is_null() {
+        unsafe {
+            NISysCfgFreeDetailedString(detailed);
+        }
+    }
+}
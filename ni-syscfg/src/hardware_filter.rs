@@ -1,15 +1,21 @@
 //! Functions to support the hardware filters when searching for hardware resources.
 //!
 use crate::error::{api_status, Result};
+use crate::experts::ExpertType;
 use crate::handles::close_handle;
+use crate::parameters::{ApiBool, BusType};
+use crate::resources::ServiceType;
 use crate::Session;
 use ni_syscfg_sys::*;
+use paste::paste;
+use std::ffi::CString;
 use std::ptr::null_mut;
 
 /// Used with [Session::find_hardware](crate::Session::find_hardware) to specify which pieces of hardware are of interest.
 pub struct HardwareFilter {
     handle: NISysCfgFilterHandle,
     mode: FilterMode,
+    properties: Vec<FilterProperty>,
 }
 
 impl HardwareFilter {
@@ -21,6 +27,7 @@ impl HardwareFilter {
         Ok(Self {
             handle,
             mode: FilterMode::MatchValuesAll,
+            properties: Vec::new(),
         })
     }
 
@@ -34,15 +41,109 @@ impl HardwareFilter {
         self.mode
     }
 
+    /// Add a property for the filter to match against.
+    ///
+    /// The typed `set_*` methods are shorthands for this.
+    pub fn set_property(&mut self, property: FilterProperty) -> Result<&mut Self> {
+        property.apply(&mut NativeFilter(self.handle))?;
+        self.properties.push(property);
+        Ok(self)
+    }
+
+    /// The properties which have been set on the filter.
+    pub fn properties(&self) -> &[FilterProperty] {
+        &self.properties
+    }
+
     pub(crate) fn handle(&self) -> NISysCfgFilterHandle {
         self.handle
     }
 }
 
+/// Ready-made filters for common classes of hardware.
+///
+/// Each preset matches resources with all of its properties ([FilterMode::MatchValuesAll]).
+/// More properties can be added to narrow the match further, for example
+/// `set_is_simulated(false)` to exclude simulated devices.
+impl HardwareFilter {
+    /// Create a filter with the `mode` and `properties` already set.
+    pub fn with_properties(
+        session: &Session,
+        mode: FilterMode,
+        properties: impl IntoIterator<Item = FilterProperty>,
+    ) -> Result<Self> {
+        let mut filter = Self::new(session)?;
+        filter.set_mode(mode);
+        for property in properties {
+            filter.set_property(property)?;
+        }
+        Ok(filter)
+    }
+
+    fn from_preset(session: &Session, preset: Preset) -> Result<Self> {
+        Self::with_properties(session, FilterMode::MatchValuesAll, preset.properties())
+    }
+
+    /// NI devices which are physically present and have a driver installed.
+    ///
+    /// Excludes chassis, devices which have been removed but are still remembered by the
+    /// system and devices with no driver. Simulated devices are included.
+    pub fn present_ni_devices_with_drivers(session: &Session) -> Result<Self> {
+        Self::from_preset(session, Preset::PresentNiDevicesWithDrivers)
+    }
+
+    /// Chassis only, whether or not they are present.
+    pub fn chassis_only(session: &Session) -> Result<Self> {
+        Self::from_preset(session, Preset::ChassisOnly)
+    }
+
+    /// Devices which support internal or external calibration.
+    pub fn calibratable_devices(session: &Session) -> Result<Self> {
+        Self::from_preset(session, Preset::CalibratableDevices)
+    }
+
+    /// Any resource which supports firmware updates, including controllers and chassis.
+    pub fn firmware_updatable(session: &Session) -> Result<Self> {
+        Self::from_preset(session, Preset::FirmwareUpdatable)
+    }
+
+    /// The resource with the serial number `serial`, matched exactly as the device reports it.
+    pub fn by_serial(session: &Session, serial: &str) -> Result<Self> {
+        Self::from_preset(session, Preset::BySerial(serial.to_string()))
+    }
+}
+
+/// The properties behind each of the [HardwareFilter] presets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Preset {
+    PresentNiDevicesWithDrivers,
+    ChassisOnly,
+    CalibratableDevices,
+    FirmwareUpdatable,
+    BySerial(String),
+}
+
+impl Preset {
+    fn properties(self) -> Vec<FilterProperty> {
+        use FilterProperty::*;
+        match self {
+            Self::PresentNiDevicesWithDrivers => vec![
+                IsDevice(true),
+                IsNiProduct(true),
+                IsPresent(true),
+                HasDriver(true),
+            ],
+            Self::ChassisOnly => vec![IsChassis(true)],
+            Self::CalibratableDevices => vec![IsDevice(true), SupportsCalibration(true)],
+            Self::FirmwareUpdatable => vec![SupportsFirmwareUpdate(true)],
+            Self::BySerial(serial) => vec![SerialNumber(serial)],
+        }
+    }
+}
+
 impl Drop for HardwareFilter {
     fn drop(&mut self) {
         let _ = close_handle(self.handle);
-        println!("Drop Filter");
     }
 }
 
@@ -59,3 +160,463 @@ pub enum FilterMode {
     /// Includes all of the properties specified in the input filter, regardless of the values.
     AllPropertiesExist = NISysCfgFilterMode_NISysCfgFilterModeAllPropertiesExist,
 }
+
+/// A property which a [HardwareFilter] can match resources against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterProperty {
+    /// Whether the resource is a device rather than a chassis, bus or other item.
+    IsDevice(bool),
+    IsChassis(bool),
+    ServiceType(ServiceType),
+    ConnectsToBusType(BusType),
+    ConnectsToLinkName(String),
+    ProvidesBusType(BusType),
+    VendorId(u32),
+    ProductId(u32),
+    SerialNumber(String),
+    IsNiProduct(bool),
+    IsSimulated(bool),
+    SlotNumber(i32),
+    /// Whether a driver is installed for the resource.
+    HasDriver(bool),
+    /// Whether the resource is physically present in the system.
+    IsPresent(bool),
+    SupportsCalibration(bool),
+    SupportsFirmwareUpdate(bool),
+    ProvidesLinkName(String),
+    ExpertName(ExpertType),
+    ResourceName(String),
+    UserAlias(String),
+}
+
+/// The value of a filter property in the form passed to the API.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum FilterValue {
+    Int(i32),
+    UnsignedInt(u32),
+    String(CString),
+}
+
+/// Somewhere filter properties can be set, allowing the filter to be tested without the API.
+pub(crate) trait FilterBackend {
+    fn set_filter_property(&mut self, id: NISysCfgFilterProperty, value: FilterValue)
+        -> Result<()>;
+}
+
+/// Sets filter properties on a filter handle through the API.
+struct NativeFilter(NISysCfgFilterHandle);
+
+impl FilterBackend for NativeFilter {
+    fn set_filter_property(
+        &mut self,
+        id: NISysCfgFilterProperty,
+        value: FilterValue,
+    ) -> Result<()> {
+        unsafe {
+            match value {
+                FilterValue::Int(value) => {
+                    api_status(NISysCfgSetFilterProperty(self.0, id, value))?;
+                }
+                FilterValue::UnsignedInt(value) => {
+                    api_status(NISysCfgSetFilterProperty(self.0, id, value))?;
+                }
+                FilterValue::String(value) => {
+                    api_status(NISysCfgSetFilterProperty(self.0, id, value.as_ptr()))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn bool_value(value: bool) -> FilterValue {
+    FilterValue::Int(ApiBool::from(value) as i32)
+}
+
+fn string_value(value: &str) -> Result<FilterValue> {
+    Ok(FilterValue::String(CString::new(value)?))
+}
+
+impl FilterProperty {
+    /// The API property ID and value to set for this property.
+    fn to_api(&self) -> Result<(NISysCfgFilterProperty, FilterValue)> {
+        use FilterProperty::*;
+        Ok(match self {
+            IsDevice(value) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsDevice,
+                bool_value(*value),
+            ),
+            IsChassis(value) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsChassis,
+                bool_value(*value),
+            ),
+            ServiceType(service) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyServiceType,
+                FilterValue::Int(*service as i32),
+            ),
+            ConnectsToBusType(bus) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyConnectsToBusType,
+                FilterValue::Int(*bus as i32),
+            ),
+            ConnectsToLinkName(name) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyConnectsToLinkName,
+                string_value(name)?,
+            ),
+            ProvidesBusType(bus) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyProvidesBusType,
+                FilterValue::Int(*bus as i32),
+            ),
+            VendorId(id) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyVendorId,
+                FilterValue::UnsignedInt(*id),
+            ),
+            ProductId(id) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyProductId,
+                FilterValue::UnsignedInt(*id),
+            ),
+            SerialNumber(serial) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertySerialNumber,
+                string_value(serial)?,
+            ),
+            IsNiProduct(value) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsNIProduct,
+                bool_value(*value),
+            ),
+            IsSimulated(value) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsSimulated,
+                bool_value(*value),
+            ),
+            SlotNumber(slot) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertySlotNumber,
+                FilterValue::Int(*slot),
+            ),
+            HasDriver(installed) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyHasDriver,
+                FilterValue::Int(if *installed {
+                    NISysCfgHasDriverType_NISysCfgHasDriverTypeInstalled
+                } else {
+                    NISysCfgHasDriverType_NISysCfgHasDriverTypeNotInstalled
+                }),
+            ),
+            IsPresent(present) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsPresent,
+                FilterValue::Int(if *present {
+                    NISysCfgIsPresentType_NISysCfgIsPresentTypePresent
+                } else {
+                    NISysCfgIsPresentType_NISysCfgIsPresentTypeNotPresent
+                }),
+            ),
+            SupportsCalibration(value) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertySupportsCalibration,
+                bool_value(*value),
+            ),
+            SupportsFirmwareUpdate(value) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertySupportsFirmwareUpdate,
+                bool_value(*value),
+            ),
+            ProvidesLinkName(name) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyProvidesLinkName,
+                string_value(name)?,
+            ),
+            ExpertName(expert) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyExpertName,
+                string_value(&expert.to_programmatic_string())?,
+            ),
+            ResourceName(name) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyResourceName,
+                string_value(name)?,
+            ),
+            UserAlias(alias) => (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyUserAlias,
+                string_value(alias)?,
+            ),
+        })
+    }
+
+    /// Set this property on the `backend`.
+    pub(crate) fn apply(&self, backend: &mut impl FilterBackend) -> Result<()> {
+        let (id, value) = self.to_api()?;
+        backend.set_filter_property(id, value)
+    }
+}
+
+/// Generates a typed `set_*` method on [HardwareFilter] for each [FilterProperty].
+macro_rules! filter_setters {
+    ( $( $(#[$doc:meta])* $property:ident => $name:ident ( $value:ty ) ),* $(,)? ) => {
+        impl HardwareFilter {
+            $(
+                paste! {
+                    $(#[$doc])*
+                    pub fn [<set_ $name>](&mut self, value: $value) -> Result<&mut Self> {
+                        self.set_property(FilterProperty::$property(value.into()))
+                    }
+                }
+            )*
+        }
+    };
+}
+
+filter_setters!(
+    /// Match on whether the resource is a device rather than a chassis, bus or other item.
+    IsDevice => is_device(bool),
+    /// Match on whether the resource is a chassis.
+    IsChassis => is_chassis(bool),
+    /// Only match resources which provide the `service`.
+    ServiceType => service_type(ServiceType),
+    /// Match resources connected to this type of bus.
+    ConnectsToBusType => connects_to_bus_type(BusType),
+    /// Match resources connected to the link with this name.
+    ConnectsToLinkName => connects_to_link_name(&str),
+    /// Match resources which provide this type of bus.
+    ProvidesBusType => provides_bus_type(BusType),
+    /// Match on the vendor ID, e.g. `0x1093` for NI.
+    VendorId => vendor_id(u32),
+    /// Match on the product ID.
+    ProductId => product_id(u32),
+    /// Match on the serial number.
+    SerialNumber => serial_number(&str),
+    /// Match on whether the resource is an NI product.
+    IsNiProduct => is_ni_product(bool),
+    /// Match on whether the resource is a simulated device.
+    IsSimulated => is_simulated(bool),
+    /// Match resources in this slot.
+    SlotNumber => slot_number(i32),
+    /// Match on whether a driver is installed for the resource.
+    HasDriver => has_driver(bool),
+    /// Match on whether the resource is physically present.
+    IsPresent => is_present(bool),
+    /// Match on whether the resource supports calibration.
+    SupportsCalibration => supports_calibration(bool),
+    /// Match on whether the resource supports firmware updates.
+    SupportsFirmwareUpdate => supports_firmware_update(bool),
+    /// Match resources which provide the link with this name.
+    ProvidesLinkName => provides_link_name(&str),
+    /// Match resources managed by this expert.
+    ExpertName => expert_name(ExpertType),
+    /// Match on the resource name used by the expert, e.g. `Dev1`.
+    ResourceName => resource_name(&str),
+    /// Match on the user alias.
+    UserAlias => user_alias(&str),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the properties set instead of calling the API.
+    #[derive(Default)]
+    struct RecordingBackend {
+        set: Vec<(NISysCfgFilterProperty, FilterValue)>,
+    }
+
+    impl FilterBackend for RecordingBackend {
+        fn set_filter_property(
+            &mut self,
+            id: NISysCfgFilterProperty,
+            value: FilterValue,
+        ) -> Result<()> {
+            self.set.push((id, value));
+            Ok(())
+        }
+    }
+
+    fn applied(property: FilterProperty) -> (NISysCfgFilterProperty, FilterValue) {
+        let mut backend = RecordingBackend::default();
+        property.apply(&mut backend).unwrap();
+        assert_eq!(backend.set.len(), 1);
+        backend.set.remove(0)
+    }
+
+    fn string(text: &str) -> FilterValue {
+        FilterValue::String(CString::new(text).unwrap())
+    }
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(
+            Preset::PresentNiDevicesWithDrivers.properties(),
+            [
+                FilterProperty::IsDevice(true),
+                FilterProperty::IsNiProduct(true),
+                FilterProperty::IsPresent(true),
+                FilterProperty::HasDriver(true),
+            ]
+        );
+        assert_eq!(
+            Preset::BySerial("01ABC123".to_string()).properties(),
+            [FilterProperty::SerialNumber("01ABC123".to_string())]
+        );
+        assert_eq!(
+            Preset::CalibratableDevices.properties(),
+            [
+                FilterProperty::IsDevice(true),
+                FilterProperty::SupportsCalibration(true),
+            ]
+        );
+        assert_eq!(
+            Preset::ChassisOnly.properties(),
+            [FilterProperty::IsChassis(true)]
+        );
+        assert_eq!(
+            Preset::FirmwareUpdatable.properties(),
+            [FilterProperty::SupportsFirmwareUpdate(true)]
+        );
+    }
+
+    #[test]
+    fn test_bool_properties() {
+        let cases = [
+            (
+                FilterProperty::IsDevice(true),
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsDevice,
+            ),
+            (
+                FilterProperty::IsChassis(true),
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsChassis,
+            ),
+            (
+                FilterProperty::IsNiProduct(true),
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsNIProduct,
+            ),
+            (
+                FilterProperty::IsSimulated(true),
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsSimulated,
+            ),
+            (
+                FilterProperty::SupportsCalibration(true),
+                NISysCfgFilterProperty_NISysCfgFilterPropertySupportsCalibration,
+            ),
+            (
+                FilterProperty::SupportsFirmwareUpdate(true),
+                NISysCfgFilterProperty_NISysCfgFilterPropertySupportsFirmwareUpdate,
+            ),
+        ];
+        for (property, id) in cases {
+            assert_eq!(applied(property), (id, FilterValue::Int(1)));
+        }
+        assert_eq!(
+            applied(FilterProperty::IsDevice(false)),
+            (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsDevice,
+                FilterValue::Int(0)
+            )
+        );
+    }
+
+    #[test]
+    fn test_enum_properties() {
+        assert_eq!(
+            applied(FilterProperty::ServiceType(ServiceType::LocalFpga)),
+            (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyServiceType,
+                FilterValue::Int(NISysCfgServiceType_NISysCfgServiceTypeLocalFpga)
+            )
+        );
+        assert_eq!(
+            applied(FilterProperty::ConnectsToBusType(BusType::Usb)),
+            (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyConnectsToBusType,
+                FilterValue::Int(NISysCfgBusType_NISysCfgBusTypeUsb)
+            )
+        );
+        assert_eq!(
+            applied(FilterProperty::ProvidesBusType(BusType::PciPxi)),
+            (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyProvidesBusType,
+                FilterValue::Int(NISysCfgBusType_NISysCfgBusTypePciPxi)
+            )
+        );
+        assert_eq!(
+            applied(FilterProperty::HasDriver(true)),
+            (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyHasDriver,
+                FilterValue::Int(NISysCfgHasDriverType_NISysCfgHasDriverTypeInstalled)
+            )
+        );
+        assert_eq!(
+            applied(FilterProperty::IsPresent(false)),
+            (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyIsPresent,
+                FilterValue::Int(NISysCfgIsPresentType_NISysCfgIsPresentTypeNotPresent)
+            )
+        );
+    }
+
+    #[test]
+    fn test_numeric_properties() {
+        assert_eq!(
+            applied(FilterProperty::VendorId(0x1093)),
+            (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyVendorId,
+                FilterValue::UnsignedInt(0x1093)
+            )
+        );
+        assert_eq!(
+            applied(FilterProperty::ProductId(0x7514)),
+            (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyProductId,
+                FilterValue::UnsignedInt(0x7514)
+            )
+        );
+        assert_eq!(
+            applied(FilterProperty::SlotNumber(3)),
+            (
+                NISysCfgFilterProperty_NISysCfgFilterPropertySlotNumber,
+                FilterValue::Int(3)
+            )
+        );
+    }
+
+    #[test]
+    fn test_string_properties() {
+        let cases = [
+            (
+                FilterProperty::ConnectsToLinkName("PXI1".to_string()),
+                NISysCfgFilterProperty_NISysCfgFilterPropertyConnectsToLinkName,
+            ),
+            (
+                FilterProperty::SerialNumber("01ABC123".to_string()),
+                NISysCfgFilterProperty_NISysCfgFilterPropertySerialNumber,
+            ),
+            (
+                FilterProperty::ProvidesLinkName("PXI1".to_string()),
+                NISysCfgFilterProperty_NISysCfgFilterPropertyProvidesLinkName,
+            ),
+            (
+                FilterProperty::ResourceName("Dev1".to_string()),
+                NISysCfgFilterProperty_NISysCfgFilterPropertyResourceName,
+            ),
+            (
+                FilterProperty::UserAlias("Dev1".to_string()),
+                NISysCfgFilterProperty_NISysCfgFilterPropertyUserAlias,
+            ),
+        ];
+        for (property, id) in cases {
+            let expected = match &property {
+                FilterProperty::ConnectsToLinkName(text)
+                | FilterProperty::SerialNumber(text)
+                | FilterProperty::ProvidesLinkName(text)
+                | FilterProperty::ResourceName(text)
+                | FilterProperty::UserAlias(text) => string(text),
+                _ => unreachable!(),
+            };
+            assert_eq!(applied(property), (id, expected));
+        }
+        assert_eq!(
+            applied(FilterProperty::ExpertName(ExpertType::NiDaqmx)),
+            (
+                NISysCfgFilterProperty_NISysCfgFilterPropertyExpertName,
+                string("daqmx")
+            )
+        );
+    }
+
+    #[test]
+    fn test_nul_in_string_property_is_an_error() {
+        let mut backend = RecordingBackend::default();
+        assert!(FilterProperty::UserAlias("Dev\0".to_string())
+            .apply(&mut backend)
+            .is_err());
+        assert!(backend.set.is_empty());
+    }
+}
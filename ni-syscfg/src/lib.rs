@@ -3,13 +3,35 @@ mod experts;
 mod handles;
 mod hardware_filter;
 mod parameters;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+mod query;
 mod resources;
 mod session;
 pub mod software;
 pub(crate) mod types;
 mod system;
+mod telemetry;
+#[cfg(test)]
+mod test_support;
 
 pub use experts::ExpertType;
-pub use hardware_filter::{FilterMode, HardwareFilter};
+pub use hardware_filter::{FilterMode, FilterProperty, HardwareFilter};
+pub use parameters::BusType;
+pub use query::{HardwareQuery, ParseQueryError};
+pub use resources::{
+    Alarm, AlarmEvaluator, AlarmKind, CalibrationInfo, CpuInfo, CpuLoad, DeviceIdentity,
+    ExpertInfo, FirmwareFile, FirmwareFilePattern, FirmwareInfo, FirmwareProgress, FirmwareStatus,
+    FirmwareUpdate, FirmwareUpdateMode, FirmwareUpdateOptions, FirmwareVersion, HardwareNode,
+    HardwareResource, HardwareResourceList, HardwareTree, HardwareTreeEntry, IdentifiedDevice,
+    IdentityReport, LanInstrument, MacAddress, MemoryModule, ModuleProgramMode, MovedDevice,
+    ParseDeviceIdentityError, ParseMacAddressError, PciAddress, PciLink, PowerSupply,
+    PowerSupplyState, SensorKind, SensorReading, SensorReadings, ServiceType, Severity,
+    TcpInstrumentInfo,
+};
 pub use session::*;
-pub use system::RealTimeSession;
+pub use system::{
+    ControllerMemory, MemoryUsage, RealTimeSession, SlotProgramMode, SystemSummary, Usage,
+    UsageLimit,
+};
+pub use telemetry::{Metric, TelemetryConfig, TelemetryPoller, TelemetrySample, TelemetrySource};
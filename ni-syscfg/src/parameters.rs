@@ -11,12 +11,27 @@ use crate::error::{api_status, NiSystemConfigurationError, Result};
 use ni_syscfg_sys::*;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
-use std::ffi::{c_void, CString};
+use std::ffi::{c_void, CStr, CString};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::NiSysCfgApiStatus;
 
 pub fn new_simple_string() -> CString {
     CString::new(vec![1; NISYSCFG_SIMPLE_STRING_LENGTH as usize]).unwrap()
 }
 
+/// Converts the result of a property read into an [Option] where the property doesn't exist on this resource.
+///
+/// Many properties only apply to certain classes of hardware, so this allows us to
+/// treat them as absent rather than as a failure.
+pub(crate) fn optional_property<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(NiSystemConfigurationError::ApiError(NiSysCfgApiStatus::PropDoesNotExist)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
 /// Provides a common trait for all parameter types that can be read from the various parameter interfaces.
 ///
 /// This then provides a typed parameter API that can be used by the rust code.
@@ -129,6 +144,171 @@ impl ReadableParameter for String {
     }
 }
 
+/// Implements [ReadableParameter] for numeric types which the API writes directly into the value pointer.
+macro_rules! numeric_parameter {
+    ($type:ty) => {
+        impl ReadableParameter for $type {
+            fn read_resource_parameter(
+                handle: NISysCfgResourceHandle,
+                id: NISysCfgResourceProperty,
+            ) -> Result<Self> {
+                let mut value: $type = Default::default();
+                unsafe {
+                    api_status(NISysCfgGetResourceProperty(
+                        handle,
+                        id,
+                        &mut value as *mut _ as *mut c_void,
+                    ))?;
+                }
+                Ok(value)
+            }
+
+            fn read_resource_indexed_parameter(
+                handle: NISysCfgResourceHandle,
+                id: NISysCfgIndexedProperty,
+                index: u32,
+            ) -> Result<Self> {
+                let mut value: $type = Default::default();
+                unsafe {
+                    api_status(NISysCfgGetResourceIndexedProperty(
+                        handle,
+                        id,
+                        index,
+                        &mut value as *mut _ as *mut c_void,
+                    ))?;
+                }
+                Ok(value)
+            }
+
+            fn read_system_parameter(
+                handle: NISysCfgSessionHandle,
+                id: NISysCfgSystemProperty,
+            ) -> Result<Self> {
+                let mut value: $type = Default::default();
+                unsafe {
+                    api_status(NISysCfgGetSystemProperty(
+                        handle,
+                        id,
+                        &mut value as *mut _ as *mut c_void,
+                    ))?;
+                }
+                Ok(value)
+            }
+        }
+    };
+}
+
+numeric_parameter!(u16);
+numeric_parameter!(u32);
+numeric_parameter!(f64);
+
+/// Timestamps are read as the API's UTC timestamp type and then converted to a [SystemTime].
+impl ReadableParameter for SystemTime {
+    fn read_resource_parameter(
+        handle: NISysCfgResourceHandle,
+        id: NISysCfgResourceProperty,
+    ) -> Result<Self> {
+        let mut value = NISysCfgTimestampUTC { u32Data: [0; 4] };
+        unsafe {
+            api_status(NISysCfgGetResourceProperty(
+                handle,
+                id,
+                &mut value as *mut _ as *mut c_void,
+            ))?;
+        }
+        timestamp_to_system_time(&value)
+    }
+
+    fn read_resource_indexed_parameter(
+        handle: NISysCfgResourceHandle,
+        id: NISysCfgIndexedProperty,
+        index: u32,
+    ) -> Result<Self> {
+        let mut value = NISysCfgTimestampUTC { u32Data: [0; 4] };
+        unsafe {
+            api_status(NISysCfgGetResourceIndexedProperty(
+                handle,
+                id,
+                index,
+                &mut value as *mut _ as *mut c_void,
+            ))?;
+        }
+        timestamp_to_system_time(&value)
+    }
+
+    fn read_system_parameter(
+        handle: NISysCfgSessionHandle,
+        id: NISysCfgSystemProperty,
+    ) -> Result<Self> {
+        let mut value = NISysCfgTimestampUTC { u32Data: [0; 4] };
+        unsafe {
+            api_status(NISysCfgGetSystemProperty(
+                handle,
+                id,
+                &mut value as *mut _ as *mut c_void,
+            ))?;
+        }
+        timestamp_to_system_time(&value)
+    }
+}
+
+fn timestamp_to_system_time(timestamp: &NISysCfgTimestampUTC) -> Result<SystemTime> {
+    let mut seconds: NISysCfgUInt64 = 0;
+    let mut fractional = 0f64;
+    unsafe {
+        api_status(NISysCfgValuesFromTimestamp(
+            timestamp,
+            &mut seconds,
+            &mut fractional,
+        ))?;
+    }
+    Ok(system_time_from_values(seconds, fractional))
+}
+
+/// Build the [SystemTime] from the seconds since the unix epoch and fractional seconds the API provides.
+fn system_time_from_values(seconds: u64, fractional: f64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(seconds) + Duration::from_secs_f64(fractional.clamp(0.0, 1.0))
+}
+
+/// Provides a common trait for parameter types which can be written to the resource properties.
+///
+/// Writes are only held on the resource handle until the changes are saved.
+pub trait WriteableParameter {
+    fn write_resource_parameter(
+        &self,
+        handle: NISysCfgResourceHandle,
+        id: NISysCfgResourceProperty,
+    ) -> Result<()>;
+}
+
+impl WriteableParameter for i32 {
+    fn write_resource_parameter(
+        &self,
+        handle: NISysCfgResourceHandle,
+        id: NISysCfgResourceProperty,
+    ) -> Result<()> {
+        unsafe {
+            api_status(NISysCfgSetResourceProperty(handle, id, *self))?;
+        }
+        Ok(())
+    }
+}
+
+/// Strings are written from a [CStr] so the caller controls the lifetime of the buffer, which
+/// allows secrets to be cleared after use.
+impl WriteableParameter for CStr {
+    fn write_resource_parameter(
+        &self,
+        handle: NISysCfgResourceHandle,
+        id: NISysCfgResourceProperty,
+    ) -> Result<()> {
+        unsafe {
+            api_status(NISysCfgSetResourceProperty(handle, id, self.as_ptr()))?;
+        }
+        Ok(())
+    }
+}
+
 /// Marker trait for enums to be used as property values.
 pub(crate) trait ValueEnum: FromPrimitive {}
 
@@ -194,7 +374,7 @@ where
 }
 
 #[repr(i32)]
-#[derive(FromPrimitive, Copy, Clone, Debug)]
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum BusType {
     BuiltIn = NISysCfgBusType_NISysCfgBusTypeBuiltIn,
     PciPxi = NISysCfgBusType_NISysCfgBusTypePciPxi,
@@ -273,3 +453,33 @@ impl From<ApiBool> for bool {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_time_from_values() {
+        let time = system_time_from_values(1_600_000_000, 0.5);
+        assert_eq!(
+            time.duration_since(UNIX_EPOCH).unwrap(),
+            Duration::from_millis(1_600_000_000_500)
+        );
+    }
+
+    #[test]
+    fn test_optional_property_missing() {
+        let result: Result<i32> = Err(NiSystemConfigurationError::ApiError(
+            NiSysCfgApiStatus::PropDoesNotExist,
+        ));
+        assert_eq!(optional_property(result).unwrap(), None);
+    }
+
+    #[test]
+    fn test_optional_property_other_error() {
+        let result: Result<i32> = Err(NiSystemConfigurationError::ApiError(
+            NiSysCfgApiStatus::Fail,
+        ));
+        assert!(optional_property(result).is_err());
+    }
+}
@@ -0,0 +1,498 @@
+//! Exposes target health in the Prometheus text format.
+//!
+//! Enabled with the `prometheus` feature. [render] formats a [TelemetrySample] and
+//! [MetricsServer] serves the metrics of a [TelemetrySource] at `/metrics` over plain HTTP.
+//!
+//! Every metric has a `target` label. Sensor and power supply metrics also have `resource`
+//! and `sensor` or `power_supply` labels.
+
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::resources::{SensorKind, SensorReading};
+use crate::telemetry::{Metric, TelemetrySample, TelemetrySource};
+
+/// The content type of the Prometheus text exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// A metric family and its samples, written out with a single `HELP` and `TYPE`.
+struct Family {
+    name: &'static str,
+    help: &'static str,
+    samples: Vec<(Vec<(&'static str, String)>, f64)>,
+}
+
+impl Family {
+    fn new(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            samples: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, labels: Vec<(&'static str, String)>, value: f64) {
+        self.samples.push((labels, value));
+    }
+
+    fn write(&self, out: &mut String) {
+        if self.samples.is_empty() {
+            return;
+        }
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} gauge", self.name);
+        for (labels, value) in &self.samples {
+            let labels = labels
+                .iter()
+                .map(|(name, value)| format!("{name}=\"{}\"", escape_label(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = writeln!(out, "{}{{{labels}}} {}", self.name, format_value(*value));
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Format the `sample` from `target` in the Prometheus text exposition format.
+pub fn render(target: &str, sample: &TelemetrySample) -> String {
+    let target_label = || vec![("target", target.to_string())];
+
+    let mut cpu_load = Family::new(
+        "ni_syscfg_cpu_load_percent",
+        "Total load on the logical processor.",
+    );
+    let mut cpu_interrupt_load = Family::new(
+        "ni_syscfg_cpu_interrupt_load_percent",
+        "Load from handling interrupts on the logical processor.",
+    );
+    let mut cpu_speed = Family::new(
+        "ni_syscfg_cpu_speed_mhz",
+        "Clock speed of the logical processor.",
+    );
+    for load in sample.cpu.iter().flatten() {
+        let labels = || {
+            vec![
+                ("target", target.to_string()),
+                ("cpu", load.processor.to_string()),
+            ]
+        };
+        cpu_load.add(labels(), load.total_load as f64);
+        cpu_interrupt_load.add(labels(), load.interrupt_load as f64);
+        cpu_speed.add(labels(), load.speed_mhz as f64);
+    }
+
+    let mut memory_total = Family::new("ni_syscfg_memory_total_bytes", "Total physical memory.");
+    let mut memory_free = Family::new("ni_syscfg_memory_free_bytes", "Free physical memory.");
+    let mut virtual_total = Family::new(
+        "ni_syscfg_virtual_memory_total_bytes",
+        "Total virtual memory.",
+    );
+    let mut virtual_free = Family::new(
+        "ni_syscfg_virtual_memory_free_bytes",
+        "Free virtual memory.",
+    );
+    let mut largest_block = Family::new(
+        "ni_syscfg_memory_largest_free_block_bytes",
+        "Largest contiguous block of free memory.",
+    );
+    if let Some(memory) = &sample.memory {
+        memory_total.add(target_label(), memory.physical.total_bytes as f64);
+        memory_free.add(target_label(), memory.physical.free_bytes as f64);
+        if let Some(virtual_memory) = &memory.virtual_memory {
+            virtual_total.add(target_label(), virtual_memory.total_bytes as f64);
+            virtual_free.add(target_label(), virtual_memory.free_bytes as f64);
+        }
+        if let Some(block) = memory.largest_free_block {
+            largest_block.add(target_label(), block as f64);
+        }
+    }
+
+    let mut disk_total = Family::new("ni_syscfg_disk_total_bytes", "Size of the primary disk.");
+    let mut disk_free = Family::new(
+        "ni_syscfg_disk_free_bytes",
+        "Free space on the primary disk.",
+    );
+    if let Some(disk) = &sample.disk {
+        disk_total.add(target_label(), disk.total_bytes as f64);
+        disk_free.add(target_label(), disk.free_bytes as f64);
+    }
+
+    let mut temperature = Family::new(
+        "ni_syscfg_temperature_celsius",
+        "Temperature sensor reading.",
+    );
+    let mut voltage = Family::new("ni_syscfg_voltage_volts", "Voltage sensor reading.");
+    let mut fan = Family::new("ni_syscfg_fan_speed_rpm", "Fan speed.");
+    let mut power = Family::new("ni_syscfg_power_watts", "Power sensor reading.");
+    let mut current_temp = Family::new(
+        "ni_syscfg_current_temperature_celsius",
+        "Temperature of devices which report a single temperature.",
+    );
+    for (resource, readings) in sample.sensors.iter().flatten() {
+        for reading in readings.iter() {
+            let family = match reading.kind {
+                SensorKind::Temperature => &mut temperature,
+                SensorKind::Voltage => &mut voltage,
+                SensorKind::Fan => &mut fan,
+                SensorKind::Power => &mut power,
+            };
+            family.add(sensor_labels(target, resource, reading), reading.value);
+        }
+        if let Some(value) = readings.current_temp {
+            current_temp.add(
+                vec![
+                    ("target", target.to_string()),
+                    ("resource", resource.clone()),
+                ],
+                value,
+            );
+        }
+    }
+
+    let mut supply_state = Family::new(
+        "ni_syscfg_power_supply_state",
+        "State code of the power supply as reported by the API.",
+    );
+    let mut supply_ok = Family::new(
+        "ni_syscfg_power_supply_ok",
+        "1 if the power supply is on with no faults or alerts.",
+    );
+    for (resource, supplies) in sample.power_supplies.iter().flatten() {
+        for supply in supplies {
+            let labels = || {
+                vec![
+                    ("target", target.to_string()),
+                    ("resource", resource.clone()),
+                    ("power_supply", supply.name.clone()),
+                ]
+            };
+            supply_state.add(labels(), supply.state_code as f64);
+            supply_ok.add(labels(), if supply.is_ok() { 1.0 } else { 0.0 });
+        }
+    }
+
+    let mut errors = Family::new(
+        "ni_syscfg_scrape_errors",
+        "Number of metric groups which failed to read.",
+    );
+    errors.add(target_label(), sample.errors.len() as f64);
+
+    let mut out = String::new();
+    for family in [
+        cpu_load,
+        cpu_interrupt_load,
+        cpu_speed,
+        memory_total,
+        memory_free,
+        virtual_total,
+        virtual_free,
+        largest_block,
+        disk_total,
+        disk_free,
+        temperature,
+        voltage,
+        fan,
+        power,
+        current_temp,
+        supply_state,
+        supply_ok,
+        errors,
+    ] {
+        family.write(&mut out);
+    }
+    out
+}
+
+fn sensor_labels(
+    target: &str,
+    resource: &str,
+    reading: &SensorReading,
+) -> Vec<(&'static str, String)> {
+    vec![
+        ("target", target.to_string()),
+        ("resource", resource.to_string()),
+        ("sensor", reading.name.clone()),
+    ]
+}
+
+/// How long a client has to send its request by default.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A minimal HTTP server which answers `GET /metrics` with the current metrics of a target.
+///
+/// Requests are handled one at a time on the calling thread, and each scrape reads every
+/// metric fresh from the source. A client which doesn't send its request within the
+/// [MetricsServer::request_timeout] gets a `408` so it can't hold up other scrapes.
+///
+/// # Example
+/// ```no_run
+/// use ni_syscfg::prometheus::MetricsServer;
+/// use ni_syscfg::SessionConfig;
+///
+/// let session = SessionConfig::new().target("192.168.0.10").connect().unwrap();
+/// let server = MetricsServer::bind("127.0.0.1:9100", "192.168.0.10", session).unwrap();
+/// server.serve().unwrap();
+/// ```
+pub struct MetricsServer<S: TelemetrySource> {
+    listener: TcpListener,
+    target: String,
+    source: S,
+    request_timeout: Duration,
+}
+
+impl<S: TelemetrySource> MetricsServer<S> {
+    /// Listen on `address`, labelling the metrics from `source` with `target`.
+    pub fn bind(address: impl ToSocketAddrs, target: &str, source: S) -> Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+            target: target.to_string(),
+            source,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        })
+    }
+
+    /// How long to wait for a client to send its request, and for it to accept the response.
+    ///
+    /// Defaults to 5 seconds.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// The address the server is listening on, useful when binding to port 0.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Answer requests until the listener fails, returning the listener's error.
+    ///
+    /// Errors on a single connection are ignored.
+    pub fn serve(&self) -> Result<()> {
+        loop {
+            let (stream, _) = self.listener.accept()?;
+            let _ = self.respond(stream);
+        }
+    }
+
+    /// Accept and answer a single request.
+    pub fn handle_next(&self) -> Result<()> {
+        let (stream, _) = self.listener.accept()?;
+        self.respond(stream)
+    }
+
+    fn respond(&self, mut stream: TcpStream) -> Result<()> {
+        stream.set_write_timeout(Some(self.request_timeout))?;
+        let request_line = match read_request(&stream, Instant::now() + self.request_timeout) {
+            Ok(line) => line,
+            Err(error) if is_timeout(&error) => {
+                return write_response(
+                    &mut stream,
+                    "408 Request Timeout",
+                    "Timed out reading the request\n",
+                );
+            }
+            Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+                return write_response(&mut stream, "400 Bad Request", "Malformed request\n");
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next();
+        // The query string doesn't change the response.
+        let path = parts.next().and_then(|target| target.split('?').next());
+        match (method, path) {
+            (Some("GET"), Some("/metrics")) => {
+                let sample = TelemetrySample::collect(&self.source, &Metric::ALL);
+                write_response(&mut stream, "200 OK", &render(&self.target, &sample))
+            }
+            (Some("GET"), Some(_)) => {
+                write_response(&mut stream, "404 Not Found", "Not found. Try /metrics\n")
+            }
+            _ => write_response(
+                &mut stream,
+                "405 Method Not Allowed",
+                "Only GET is supported\n",
+            ),
+        }
+    }
+}
+
+/// The longest request or header line accepted, including the line ending.
+const MAX_LINE_LENGTH: usize = 8192;
+
+/// Read the request line and skip the headers, giving up at the `deadline`.
+///
+/// Lines longer than [MAX_LINE_LENGTH] are rejected as `InvalidData`, as is text which isn't UTF-8.
+fn read_request(stream: &TcpStream, deadline: Instant) -> io::Result<String> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    let mut line = String::new();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        stream.set_read_timeout(Some(remaining))?;
+
+        line.clear();
+        let length = (&mut reader)
+            .take(MAX_LINE_LENGTH as u64)
+            .read_line(&mut line)?;
+        if length == MAX_LINE_LENGTH && !line.ends_with('\n') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request line too long",
+            ));
+        }
+        // The headers end at a blank line, or when the client stops sending.
+        if length == 0 || line.trim_end().is_empty() {
+            return Ok(request_line);
+        }
+        if request_line.is_empty() {
+            request_line = line.clone();
+        }
+    }
+}
+
+/// Socket timeouts are reported as `WouldBlock` on some platforms and `TimedOut` on others.
+fn is_timeout(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+fn write_response(stream: &mut TcpStream, status: &str, body: &str) -> Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::StubSource;
+    use std::thread;
+
+    fn rendered() -> String {
+        render(
+            "pxi-01",
+            &TelemetrySample::collect(&StubSource::default(), &Metric::ALL),
+        )
+    }
+
+    #[test]
+    fn test_render_families() {
+        let text = rendered();
+        assert!(text.contains(
+            "# HELP ni_syscfg_cpu_load_percent Total load on the logical processor.\n\
+             # TYPE ni_syscfg_cpu_load_percent gauge\n\
+             ni_syscfg_cpu_load_percent{target=\"pxi-01\",cpu=\"0\"} 35\n"
+        ));
+        assert!(text.contains("ni_syscfg_memory_free_bytes{target=\"pxi-01\"} 4096\n"));
+        assert!(text.contains(
+            "ni_syscfg_fan_speed_rpm{target=\"pxi-01\",resource=\"PXI1Chassis1\",sensor=\"Fan 1\"} 1800\n"
+        ));
+        assert!(text.contains(
+            "ni_syscfg_power_supply_ok{target=\"pxi-01\",resource=\"PXI1Chassis1\",power_supply=\"PS1\"} 0\n"
+        ));
+        assert!(text.contains("ni_syscfg_power_supply_state{target=\"pxi-01\",resource=\"PXI1Chassis1\",power_supply=\"PS1\"} 61\n"));
+    }
+
+    #[test]
+    fn test_render_skips_missing_metrics_and_counts_errors() {
+        let text = rendered();
+        assert!(!text.contains("ni_syscfg_disk_total_bytes"));
+        assert!(!text.contains("ni_syscfg_virtual_memory_total_bytes"));
+        assert!(text.contains("ni_syscfg_scrape_errors{target=\"pxi-01\"} 1\n"));
+    }
+
+    #[test]
+    fn test_labels_are_escaped() {
+        assert!(rendered().contains("sensor=\"Intake \\\"A\\\"\"} 31.5\n"));
+        assert_eq!(format_value(f64::NAN), "NaN");
+        assert_eq!(format_value(f64::NEG_INFINITY), "-Inf");
+    }
+
+    fn request(server: MetricsServer<StubSource>, request: &str) -> String {
+        let address = server.local_addr().unwrap();
+        let handle = thread::spawn(move || server.handle_next().unwrap());
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        handle.join().unwrap();
+        response
+    }
+
+    #[test]
+    fn test_metrics_endpoint() {
+        let server = MetricsServer::bind("127.0.0.1:0", "pxi-01", StubSource::default()).unwrap();
+        let response = request(server, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n");
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4\r\n"));
+        assert!(response.ends_with(&rendered()));
+    }
+
+    #[test]
+    fn test_query_string_is_ignored() {
+        let server = MetricsServer::bind("127.0.0.1:0", "pxi-01", StubSource::default()).unwrap();
+        let response = request(server, "GET /metrics?x=1 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    }
+
+    #[test]
+    fn test_other_paths_are_not_found() {
+        let server = MetricsServer::bind("127.0.0.1:0", "pxi-01", StubSource::default()).unwrap();
+        let response = request(server, "GET / HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let server = MetricsServer::bind("127.0.0.1:0", "pxi-01", StubSource::default()).unwrap();
+        let response = request(server, "POST /metrics HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    }
+
+    #[test]
+    fn test_long_lines_are_rejected() {
+        let server = MetricsServer::bind("127.0.0.1:0", "pxi-01", StubSource::default()).unwrap();
+        let response = request(server, &"A".repeat(MAX_LINE_LENGTH));
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    }
+
+    #[test]
+    fn test_stalled_clients_time_out() {
+        for stalled in ["", "GET /metrics HTTP/1.1\r\nHost: localhost\r\n"] {
+            let server = MetricsServer::bind("127.0.0.1:0", "pxi-01", StubSource::default())
+                .unwrap()
+                .request_timeout(Duration::from_millis(50));
+            let response = request(server, stalled);
+            assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+        }
+    }
+}
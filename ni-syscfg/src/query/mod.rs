@@ -0,0 +1,436 @@
+//! Hardware queries which combine filter properties with `and`, `or` and `not`.
+//!
+//! The native [HardwareFilter] only supports a single flat match, so as much of the query
+//! as possible is pushed into the filter and the rest is checked on each resource found.
+//! Queries can also be parsed from text, see [HardwareQuery::from_str].
+
+mod parse;
+
+pub use parse::ParseQueryError;
+
+use std::collections::HashSet;
+use std::mem;
+use std::ops::Not;
+
+use crate::error::Result;
+use crate::hardware_filter::{FilterMode, FilterProperty};
+use crate::resources::HardwareResource;
+use crate::Session;
+
+/// A query for hardware resources built from [FilterProperty] predicates.
+///
+/// Queries can be built in code or parsed from text such as
+/// `vendor_id=0x1093 and bus=usb and not simulated`.
+///
+/// # Example
+/// ```
+/// use ni_syscfg::{BusType, FilterProperty, HardwareQuery, SessionConfig};
+///
+/// let session = SessionConfig::new().connect().unwrap();
+///
+/// // NI USB devices or any PXI chassis, excluding simulated devices.
+/// let query = HardwareQuery::from(FilterProperty::VendorId(0x1093))
+///   .and(FilterProperty::ConnectsToBusType(BusType::Usb))
+///   .or(FilterProperty::IsChassis(true))
+///   .and(!HardwareQuery::from(FilterProperty::IsSimulated(true)));
+///
+/// for hardware in session.find_hardware_matching(&query).unwrap() {
+///   println!("Found {}", hardware.name().unwrap());
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HardwareQuery {
+    /// Matches resources with this property value.
+    Property(FilterProperty),
+    /// Matches resources which match all of the queries. An empty list matches everything.
+    And(Vec<HardwareQuery>),
+    /// Matches resources which match any of the queries. An empty list matches nothing.
+    Or(Vec<HardwareQuery>),
+    /// Matches resources which don't match the query.
+    Not(Box<HardwareQuery>),
+}
+
+impl From<FilterProperty> for HardwareQuery {
+    fn from(property: FilterProperty) -> Self {
+        Self::Property(property)
+    }
+}
+
+impl Not for HardwareQuery {
+    type Output = HardwareQuery;
+
+    /// Matches resources which don't match this query.
+    fn not(self) -> Self::Output {
+        match self {
+            Self::Not(inner) => *inner,
+            query => Self::Not(Box::new(query)),
+        }
+    }
+}
+
+impl HardwareQuery {
+    /// A query which matches every resource.
+    pub fn all() -> Self {
+        Self::And(Vec::new())
+    }
+
+    /// Matches resources which match both this query and `other`.
+    pub fn and(self, other: impl Into<HardwareQuery>) -> Self {
+        let mut queries = match self {
+            Self::And(queries) => queries,
+            query => vec![query],
+        };
+        match other.into() {
+            Self::And(other) => queries.extend(other),
+            other => queries.push(other),
+        }
+        Self::And(queries)
+    }
+
+    /// Matches resources which match either this query or `other`.
+    pub fn or(self, other: impl Into<HardwareQuery>) -> Self {
+        let mut queries = match self {
+            Self::Or(queries) => queries,
+            query => vec![query],
+        };
+        match other.into() {
+            Self::Or(other) => queries.extend(other),
+            other => queries.push(other),
+        }
+        Self::Or(queries)
+    }
+
+    /// Evaluate the query, using `check` to test each property.
+    pub(crate) fn evaluate(
+        &self,
+        check: &mut impl FnMut(&FilterProperty) -> Result<bool>,
+    ) -> Result<bool> {
+        match self {
+            Self::Property(property) => check(property),
+            Self::And(queries) => {
+                for query in queries {
+                    if !query.evaluate(check)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Self::Or(queries) => {
+                for query in queries {
+                    if query.evaluate(check)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Self::Not(query) => Ok(!query.evaluate(check)?),
+        }
+    }
+
+    /// Split the query into the part the native filter can apply and the part checked on each resource.
+    ///
+    /// The native filter holds one value per property kind, so repeated kinds are checked on the client.
+    fn plan(&self) -> QueryPlan {
+        match self {
+            Self::Property(property) => {
+                QueryPlan::native(FilterMode::MatchValuesAll, vec![property.clone()])
+            }
+            // Any one of the repeats could be the match, so none of the group can go to the filter.
+            Self::Or(queries) => match properties_only(queries) {
+                Some(properties) if !properties.is_empty() && !has_repeated_kinds(&properties) => {
+                    QueryPlan::native(FilterMode::MatchValuesAny, properties)
+                }
+                _ => QueryPlan::client_side(self.clone()),
+            },
+            Self::Not(query) => match query.as_ref() {
+                Self::Property(property) => {
+                    QueryPlan::native(FilterMode::MatchValuesNone, vec![property.clone()])
+                }
+                // Matching none of the group is matching none of each part, so repeats can be split off.
+                Self::Or(queries) => match properties_only(queries) {
+                    Some(properties) if !properties.is_empty() => {
+                        let (unique, repeated) = split_repeated_kinds(properties);
+                        QueryPlan {
+                            native: Some((FilterMode::MatchValuesNone, unique)),
+                            residual: combine(
+                                repeated.into_iter().map(Self::from).collect(),
+                                Self::Or,
+                            )
+                            .map(|query| !query),
+                        }
+                    }
+                    _ => QueryPlan::client_side(self.clone()),
+                },
+                _ => QueryPlan::client_side(self.clone()),
+            },
+            Self::And(queries) => {
+                let mut properties = Vec::new();
+                let mut remaining = Vec::new();
+                for query in queries {
+                    match query {
+                        Self::Property(property) => properties.push(property.clone()),
+                        query => remaining.push(query.clone()),
+                    }
+                }
+                let (properties, repeated) = split_repeated_kinds(properties);
+                remaining.extend(repeated.into_iter().map(Self::from));
+                QueryPlan {
+                    native: (!properties.is_empty())
+                        .then_some((FilterMode::MatchValuesAll, properties)),
+                    residual: combine(remaining, Self::And),
+                }
+            }
+        }
+    }
+}
+
+/// The properties of `queries` if they are all plain properties.
+fn properties_only(queries: &[HardwareQuery]) -> Option<Vec<FilterProperty>> {
+    queries
+        .iter()
+        .map(|query| match query {
+            HardwareQuery::Property(property) => Some(property.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Join `queries` with `group`, or return the query itself if there is only one.
+fn combine(
+    mut queries: Vec<HardwareQuery>,
+    group: fn(Vec<HardwareQuery>) -> HardwareQuery,
+) -> Option<HardwareQuery> {
+    match queries.len() {
+        0 => None,
+        1 => queries.pop(),
+        _ => Some(group(queries)),
+    }
+}
+
+fn has_repeated_kinds(properties: &[FilterProperty]) -> bool {
+    let mut kinds = HashSet::new();
+    !properties
+        .iter()
+        .all(|property| kinds.insert(mem::discriminant(property)))
+}
+
+/// Split `properties` into the first of each kind and any later properties of the same kind.
+fn split_repeated_kinds(
+    properties: Vec<FilterProperty>,
+) -> (Vec<FilterProperty>, Vec<FilterProperty>) {
+    let mut kinds = HashSet::new();
+    properties
+        .into_iter()
+        .partition(|property| kinds.insert(mem::discriminant(property)))
+}
+
+/// How a query is split between the native filter and the client.
+#[derive(Debug, PartialEq)]
+struct QueryPlan {
+    native: Option<(FilterMode, Vec<FilterProperty>)>,
+    residual: Option<HardwareQuery>,
+}
+
+impl QueryPlan {
+    fn native(mode: FilterMode, properties: Vec<FilterProperty>) -> Self {
+        Self {
+            native: Some((mode, properties)),
+            residual: None,
+        }
+    }
+
+    fn client_side(query: HardwareQuery) -> Self {
+        Self {
+            native: None,
+            residual: Some(query),
+        }
+    }
+}
+
+impl Session {
+    /// Find the hardware resources which match the `query`.
+    ///
+    /// Any part of the query the native filter can handle is applied by the API and the rest
+    /// is checked against each resource that it returns.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::{HardwareQuery, SessionConfig};
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    /// let query: HardwareQuery = "vendor_id=0x1093 and bus=usb and not simulated"
+    ///   .parse()
+    ///   .unwrap();
+    ///
+    /// let devices = session.find_hardware_matching(&query).unwrap();
+    /// ```
+    pub fn find_hardware_matching(&self, query: &HardwareQuery) -> Result<Vec<HardwareResource>> {
+        let plan = query.plan();
+
+        let filter = match plan.native {
+            Some((mode, properties)) => {
+                let mut filter = self.create_filter()?;
+                filter.set_mode(mode);
+                for property in properties {
+                    filter.set_property(property)?;
+                }
+                Some(filter)
+            }
+            None => None,
+        };
+
+        let mut found = Vec::new();
+        for hardware in self.find_hardware(filter.as_ref(), None)? {
+            let matches = match &plan.residual {
+                Some(residual) => {
+                    residual.evaluate(&mut |property| hardware.matches_property(property))?
+                }
+                None => true,
+            };
+            if matches {
+                found.push(hardware);
+            }
+        }
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parameters::BusType;
+
+    fn vendor(id: u32) -> HardwareQuery {
+        FilterProperty::VendorId(id).into()
+    }
+
+    fn usb() -> FilterProperty {
+        FilterProperty::ConnectsToBusType(BusType::Usb)
+    }
+
+    fn chassis() -> FilterProperty {
+        FilterProperty::IsChassis(true)
+    }
+
+    fn simulated() -> HardwareQuery {
+        FilterProperty::IsSimulated(true).into()
+    }
+
+    /// Evaluate the query against a resource with the given set of matching properties.
+    fn matches(query: &HardwareQuery, properties: &[FilterProperty]) -> bool {
+        query
+            .evaluate(&mut |property| Ok(properties.contains(property)))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_builder_flattens() {
+        let query = vendor(1).and(usb()).and(chassis());
+        assert_eq!(
+            query,
+            HardwareQuery::And(vec![vendor(1), usb().into(), chassis().into()])
+        );
+        assert_eq!(!!simulated(), simulated());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let query = vendor(0x1093).and(usb()).or(chassis()).and(!simulated());
+
+        assert!(matches(&query, &[FilterProperty::VendorId(0x1093), usb()]));
+        assert!(matches(&query, &[chassis()]));
+        assert!(!matches(
+            &query,
+            &[chassis(), FilterProperty::IsSimulated(true)]
+        ));
+        assert!(!matches(&query, &[FilterProperty::VendorId(0x1093)]));
+    }
+
+    #[test]
+    fn test_empty_queries() {
+        assert!(matches(&HardwareQuery::all(), &[]));
+        assert!(!matches(&HardwareQuery::Or(Vec::new()), &[]));
+    }
+
+    #[test]
+    fn test_plan_and_of_properties_is_native() {
+        let plan = vendor(1).and(usb()).plan();
+        assert_eq!(
+            plan,
+            QueryPlan::native(
+                FilterMode::MatchValuesAll,
+                vec![FilterProperty::VendorId(1), usb()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_plan_or_and_not_are_native() {
+        assert_eq!(
+            vendor(1).or(usb()).plan(),
+            QueryPlan::native(
+                FilterMode::MatchValuesAny,
+                vec![FilterProperty::VendorId(1), usb()]
+            )
+        );
+        assert_eq!(
+            (!simulated()).plan(),
+            QueryPlan::native(
+                FilterMode::MatchValuesNone,
+                vec![FilterProperty::IsSimulated(true)]
+            )
+        );
+    }
+
+    #[test]
+    fn test_plan_mixed_and_keeps_residual() {
+        let plan = vendor(1).and(usb()).and(!simulated()).plan();
+        assert_eq!(
+            plan.native,
+            Some((
+                FilterMode::MatchValuesAll,
+                vec![FilterProperty::VendorId(1), usb()]
+            ))
+        );
+        assert_eq!(plan.residual, Some(!simulated()));
+    }
+
+    #[test]
+    fn test_plan_repeated_kind_in_or_is_client_side() {
+        let query: HardwareQuery = "bus=usb or bus=pxi".parse().unwrap();
+        assert_eq!(query.plan(), QueryPlan::client_side(query.clone()));
+    }
+
+    #[test]
+    fn test_plan_repeated_kind_in_and_keeps_residual() {
+        let query: HardwareQuery = "vendor_id=1 and vendor_id=2".parse().unwrap();
+        let plan = query.plan();
+        assert_eq!(
+            plan.native,
+            Some((
+                FilterMode::MatchValuesAll,
+                vec![FilterProperty::VendorId(1)]
+            ))
+        );
+        assert_eq!(plan.residual, Some(vendor(2)));
+    }
+
+    #[test]
+    fn test_plan_repeated_kind_in_not_or_keeps_residual() {
+        let plan = (!vendor(1).or(usb()).or(vendor(2))).plan();
+        assert_eq!(
+            plan.native,
+            Some((
+                FilterMode::MatchValuesNone,
+                vec![FilterProperty::VendorId(1), usb()]
+            ))
+        );
+        assert_eq!(plan.residual, Some(!vendor(2)));
+    }
+
+    #[test]
+    fn test_plan_complex_query_is_client_side() {
+        let query = vendor(1).and(usb()).or(chassis());
+        assert_eq!(query.plan(), QueryPlan::client_side(query.clone()));
+    }
+}
@@ -0,0 +1,496 @@
+//! Parsing a [HardwareQuery] from text.
+//!
+//! The grammar is:
+//!
+//! ```text
+//! query      = and-query ("or" and-query)*
+//! and-query  = term ("and" term)*
+//! term       = "not" term | "(" query ")" | property
+//! property   = name | name "=" value | name "!=" value
+//! ```
+//!
+//! Keywords and names are case insensitive. Values are bare words or double quoted strings
+//! with `\"` and `\\` escapes. A bare name is shorthand for `name=true`.
+
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::HardwareQuery;
+use crate::experts::ExpertType;
+use crate::hardware_filter::FilterProperty;
+use crate::parameters::BusType;
+use crate::resources::ServiceType;
+
+/// The error returned when text isn't a valid [HardwareQuery].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message} at position {position}")]
+pub struct ParseQueryError {
+    /// The byte offset into the text where the problem was found.
+    pub position: usize,
+    pub message: String,
+}
+
+impl ParseQueryError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseQueryError>;
+
+impl FromStr for HardwareQuery {
+    type Err = ParseQueryError;
+
+    /// Parses a query such as `vendor_id=0x1093 and bus=usb and not simulated`.
+    ///
+    /// | Name | Value |
+    /// |------|-------|
+    /// | `device`, `chassis`, `ni_product`, `simulated` | boolean |
+    /// | `present`, `has_driver`, `calibratable`, `firmware_updatable` | boolean |
+    /// | `vendor_id`, `product_id` | number, decimal or `0x` hex |
+    /// | `slot` | number |
+    /// | `serial`, `name`, `alias`, `link`, `provides_link` | text |
+    /// | `bus`, `provides_bus` | bus type, e.g. `usb`, `pxi`, `tcpip` |
+    /// | `service` | service type, e.g. `local_system`, `mdns_lxi` |
+    /// | `expert` | expert programmatic name, e.g. `daqmx` |
+    ///
+    /// Booleans are `true`, `false`, `yes`, `no`, `1` or `0`.
+    fn from_str(text: &str) -> ParseResult<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            index: 0,
+            end: text.len(),
+        };
+        let query = parser.query()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some(token) => Err(ParseQueryError::new(
+                token.position,
+                format!("unexpected {}", token.kind.describe()),
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TokenKind {
+    OpenParen,
+    CloseParen,
+    Equals,
+    NotEquals,
+    Word(String),
+    Quoted(String),
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            Self::OpenParen => "`(`".to_string(),
+            Self::CloseParen => "`)`".to_string(),
+            Self::Equals => "`=`".to_string(),
+            Self::NotEquals => "`!=`".to_string(),
+            Self::Word(word) => format!("`{word}`"),
+            Self::Quoted(text) => format!("\"{text}\""),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Self::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | ':' | '-' | '/')
+}
+
+fn tokenize(text: &str) -> ParseResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '(' => TokenKind::OpenParen,
+            ')' => TokenKind::CloseParen,
+            '=' => TokenKind::Equals,
+            '!' if chars.next_if(|&(_, next)| next == '=').is_some() => TokenKind::NotEquals,
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, escaped @ ('"' | '\\'))) => value.push(escaped),
+                            Some((escape, _)) => {
+                                return Err(ParseQueryError::new(escape - 1, "invalid escape"))
+                            }
+                            None => {
+                                return Err(ParseQueryError::new(position, "unterminated string"))
+                            }
+                        },
+                        Some((_, c)) => value.push(c),
+                        None => return Err(ParseQueryError::new(position, "unterminated string")),
+                    }
+                }
+                TokenKind::Quoted(value)
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some((_, c)) = chars.next_if(|&(_, c)| is_word_char(c)) {
+                    word.push(c);
+                }
+                TokenKind::Word(word)
+            }
+            c => {
+                return Err(ParseQueryError::new(
+                    position,
+                    format!("unexpected character `{c}`"),
+                ))
+            }
+        };
+        tokens.push(Token { kind, position });
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    /// The position reported for errors at the end of the text.
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn next_is_keyword(&mut self, keyword: &str) -> bool {
+        let found = self
+            .peek()
+            .is_some_and(|token| token.kind.is_keyword(keyword));
+        if found {
+            self.index += 1;
+        }
+        found
+    }
+
+    fn query(&mut self) -> ParseResult<HardwareQuery> {
+        let mut query = self.and_query()?;
+        while self.next_is_keyword("or") {
+            query = query.or(self.and_query()?);
+        }
+        Ok(query)
+    }
+
+    fn and_query(&mut self) -> ParseResult<HardwareQuery> {
+        let mut query = self.term()?;
+        while self.next_is_keyword("and") {
+            query = query.and(self.term()?);
+        }
+        Ok(query)
+    }
+
+    fn term(&mut self) -> ParseResult<HardwareQuery> {
+        let Some(token) = self.next() else {
+            return Err(ParseQueryError::new(self.end, "expected a property"));
+        };
+        match token.kind {
+            TokenKind::Word(ref word) if word.eq_ignore_ascii_case("not") => Ok(!self.term()?),
+            TokenKind::OpenParen => {
+                let query = self.query()?;
+                match self.next() {
+                    Some(Token {
+                        kind: TokenKind::CloseParen,
+                        ..
+                    }) => Ok(query),
+                    Some(other) => Err(ParseQueryError::new(
+                        other.position,
+                        format!("expected `)` but found {}", other.kind.describe()),
+                    )),
+                    None => Err(ParseQueryError::new(token.position, "unclosed `(`")),
+                }
+            }
+            TokenKind::Word(name) => self.property(&name, token.position),
+            other => Err(ParseQueryError::new(
+                token.position,
+                format!("expected a property but found {}", other.describe()),
+            )),
+        }
+    }
+
+    fn property(&mut self, name: &str, position: usize) -> ParseResult<HardwareQuery> {
+        let name = name.to_ascii_lowercase();
+        let Some(kind) = property_kind(&name) else {
+            return Err(ParseQueryError::new(
+                position,
+                format!("unknown property `{name}`"),
+            ));
+        };
+
+        let negate = match self.peek().map(|token| &token.kind) {
+            Some(TokenKind::Equals) => false,
+            Some(TokenKind::NotEquals) => true,
+            _ => {
+                // A bare name is only allowed for true or false properties.
+                return match kind {
+                    PropertyKind::Bool(property) => Ok(property(true).into()),
+                    _ => Err(ParseQueryError::new(
+                        position,
+                        format!("`{name}` needs a value"),
+                    )),
+                };
+            }
+        };
+        let operator = self.next().map_or(self.end, |token| token.position);
+
+        let (value, value_position) = match self.next() {
+            Some(Token {
+                kind: TokenKind::Word(value) | TokenKind::Quoted(value),
+                position,
+            }) => (value, position),
+            Some(other) => {
+                return Err(ParseQueryError::new(
+                    other.position,
+                    format!("expected a value but found {}", other.kind.describe()),
+                ))
+            }
+            None => return Err(ParseQueryError::new(operator, "expected a value")),
+        };
+
+        let property = kind.with_value(&value).ok_or_else(|| {
+            ParseQueryError::new(
+                value_position,
+                format!("invalid value `{value}` for `{name}`"),
+            )
+        })?;
+        let query = HardwareQuery::from(property);
+        Ok(if negate { !query } else { query })
+    }
+}
+
+/// How to build a [FilterProperty] from a value for each property name.
+enum PropertyKind {
+    Bool(fn(bool) -> FilterProperty),
+    Id(fn(u32) -> FilterProperty),
+    Slot,
+    Text(fn(String) -> FilterProperty),
+    Bus(fn(BusType) -> FilterProperty),
+    Service,
+    Expert,
+}
+
+impl PropertyKind {
+    fn with_value(&self, value: &str) -> Option<FilterProperty> {
+        Some(match self {
+            Self::Bool(property) => property(parse_bool(value)?),
+            Self::Id(property) => property(parse_id(value)?),
+            Self::Slot => FilterProperty::SlotNumber(value.parse().ok()?),
+            Self::Text(property) => property(value.to_string()),
+            Self::Bus(property) => property(parse_bus_type(value)?),
+            Self::Service => FilterProperty::ServiceType(parse_service_type(value)?),
+            Self::Expert => FilterProperty::ExpertName(ExpertType::from(value)),
+        })
+    }
+}
+
+fn property_kind(name: &str) -> Option<PropertyKind> {
+    use FilterProperty as P;
+    use PropertyKind::*;
+    Some(match name {
+        "device" | "is_device" => Bool(P::IsDevice),
+        "chassis" | "is_chassis" => Bool(P::IsChassis),
+        "ni_product" | "is_ni_product" => Bool(P::IsNiProduct),
+        "simulated" | "is_simulated" => Bool(P::IsSimulated),
+        "present" | "is_present" => Bool(P::IsPresent),
+        "has_driver" => Bool(P::HasDriver),
+        "calibratable" | "supports_calibration" => Bool(P::SupportsCalibration),
+        "firmware_updatable" | "supports_firmware_update" => Bool(P::SupportsFirmwareUpdate),
+        "vendor_id" => Id(P::VendorId),
+        "product_id" => Id(P::ProductId),
+        "slot" | "slot_number" => Slot,
+        "serial" | "serial_number" => Text(P::SerialNumber),
+        "name" | "resource_name" => Text(P::ResourceName),
+        "alias" | "user_alias" => Text(P::UserAlias),
+        "link" | "connects_to_link" => Text(P::ConnectsToLinkName),
+        "provides_link" => Text(P::ProvidesLinkName),
+        "bus" | "connects_to_bus" => Bus(P::ConnectsToBusType),
+        "provides_bus" => Bus(P::ProvidesBusType),
+        "service" => Service,
+        "expert" => Expert,
+        _ => return None,
+    })
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "1" => Some(true),
+        "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_id(value: &str) -> Option<u32> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Lower case with `_` and `-` removed so `compact_daq`, `CompactDAQ` and `compactdaq` all match.
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn parse_bus_type(value: &str) -> Option<BusType> {
+    Some(match normalize(value).as_str() {
+        "builtin" => BusType::BuiltIn,
+        "pci" | "pxi" | "pcipxi" => BusType::PciPxi,
+        "usb" => BusType::Usb,
+        "gpib" => BusType::Gpib,
+        "vxi" => BusType::Vxi,
+        "serial" => BusType::Serial,
+        "tcpip" | "tcp" => BusType::TcpIp,
+        "compactrio" | "crio" => BusType::CompactRio,
+        "scxi" => BusType::Scxi,
+        "compactdaq" | "cdaq" => BusType::CompactDaq,
+        "switchblock" => BusType::SwitchBlock,
+        "scc" => BusType::Scc,
+        "firewire" => BusType::FireWire,
+        "accessory" => BusType::Accessory,
+        "can" => BusType::Can,
+        "switchblockdevice" => BusType::SwitchBlockDevice,
+        "slsc" => BusType::Slsc,
+        _ => return None,
+    })
+}
+
+fn parse_service_type(value: &str) -> Option<ServiceType> {
+    Some(match normalize(value).as_str() {
+        "mdnsnitcp" => ServiceType::MdnsNiTcp,
+        "mdnsnirealtime" => ServiceType::MdnsNiRealtime,
+        "mdnsnisysapi" => ServiceType::MdnsNiSysapi,
+        "mdnsnihttp" => ServiceType::MdnsNiHttp,
+        "localsystem" => ServiceType::LocalSystem,
+        "localnetinterface" => ServiceType::LocalNetInterface,
+        "localtimekeeper" => ServiceType::LocalTimeKeeper,
+        "localtimesource" => ServiceType::LocalTimeSource,
+        "mdnslxi" => ServiceType::MdnsLxi,
+        "localfpga" => ServiceType::LocalFpga,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> ParseResult<HardwareQuery> {
+        text.parse()
+    }
+
+    fn error_at(text: &str) -> usize {
+        parse(text).unwrap_err().position
+    }
+
+    #[test]
+    fn test_parse_example() {
+        let query = parse("vendor_id=0x1093 and bus=usb and not simulated").unwrap();
+        assert_eq!(
+            query,
+            HardwareQuery::from(FilterProperty::VendorId(0x1093))
+                .and(FilterProperty::ConnectsToBusType(BusType::Usb))
+                .and(!HardwareQuery::from(FilterProperty::IsSimulated(true)))
+        );
+    }
+
+    #[test]
+    fn test_precedence_and_grouping() {
+        let chassis = HardwareQuery::from(FilterProperty::IsChassis(true));
+        let usb = HardwareQuery::from(FilterProperty::ConnectsToBusType(BusType::Usb));
+        let pxi = HardwareQuery::from(FilterProperty::ConnectsToBusType(BusType::PciPxi));
+
+        assert_eq!(
+            parse("chassis or bus=usb and bus=pxi").unwrap(),
+            chassis.clone().or(usb.clone().and(pxi.clone()))
+        );
+        assert_eq!(
+            parse("(chassis OR bus=usb) AND bus=PXI").unwrap(),
+            chassis.or(usb).and(pxi)
+        );
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!(
+            parse("serial=\"01 \\\"AB\\\"\"").unwrap(),
+            FilterProperty::SerialNumber("01 \"AB\"".to_string()).into()
+        );
+        assert_eq!(
+            parse("product_id=30000 and slot=3").unwrap(),
+            HardwareQuery::from(FilterProperty::ProductId(30000))
+                .and(FilterProperty::SlotNumber(3))
+        );
+        assert_eq!(
+            parse("expert=ni-488.2").unwrap(),
+            FilterProperty::ExpertName(ExpertType::Ni488_2).into()
+        );
+        assert_eq!(
+            parse("service=local_system").unwrap(),
+            FilterProperty::ServiceType(ServiceType::LocalSystem).into()
+        );
+        assert_eq!(
+            parse("present=no").unwrap(),
+            FilterProperty::IsPresent(false).into()
+        );
+        assert_eq!(
+            parse("alias != PXI1Slot2").unwrap(),
+            !HardwareQuery::from(FilterProperty::UserAlias("PXI1Slot2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_error_positions() {
+        assert_eq!(error_at(""), 0);
+        assert_eq!(error_at("vendor_id=0x1093 and"), 20);
+        assert_eq!(error_at("chassis and colour=red"), 12);
+        assert_eq!(error_at("vendor_id=0xZZ"), 10);
+        assert_eq!(error_at("vendor_id"), 0);
+        assert_eq!(error_at("bus="), 3);
+        assert_eq!(error_at("(chassis or device"), 0);
+        assert_eq!(error_at("chassis device"), 8);
+        assert_eq!(error_at("chassis & device"), 8);
+        assert_eq!(error_at("serial=\"abc"), 7);
+    }
+
+    #[test]
+    fn test_error_message() {
+        assert_eq!(
+            parse("chassis and colour=red").unwrap_err().to_string(),
+            "unknown property `colour` at position 12"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Checking sensor readings against their critical limits.
+
+use std::fmt;
+
+use super::{SensorKind, SensorReading, SensorReadings};
+use crate::error::Result;
+use crate::session::Session;
+
+/// How serious an [Alarm] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The reading is within the margin of a critical limit.
+    Warning,
+    /// The reading is past a critical limit.
+    Critical,
+}
+
+/// Which limit an [Alarm] is for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AlarmKind {
+    AboveUpperCritical,
+    BelowLowerCritical,
+    NearUpperCritical,
+    NearLowerCritical,
+}
+
+/// A sensor reading which is past, or close to, one of its critical limits.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alarm {
+    /// The name of the resource the sensor is on.
+    pub resource: String,
+    /// The name of the sensor.
+    pub sensor: String,
+    pub sensor_kind: SensorKind,
+    pub kind: AlarmKind,
+    pub value: f64,
+    /// The critical limit the reading was compared against.
+    pub limit: f64,
+    pub severity: Severity,
+}
+
+impl fmt::Display for Alarm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = self.sensor_kind.unit();
+        let comparison = match self.kind {
+            AlarmKind::AboveUpperCritical => "above the upper",
+            AlarmKind::BelowLowerCritical => "below the lower",
+            AlarmKind::NearUpperCritical => "close to the upper",
+            AlarmKind::NearLowerCritical => "close to the lower",
+        };
+        write!(
+            f,
+            "{:?}: {} {} is {} {unit} {comparison} critical limit of {} {unit}",
+            self.severity, self.resource, self.sensor, self.value, self.limit,
+        )
+    }
+}
+
+/// Compares sensor readings against their critical limits to produce [Alarm]s.
+///
+/// Readings past a critical limit are [Severity::Critical]. If a margin is set for the kind
+/// of sensor, readings within that margin of a limit are [Severity::Warning]. Margins are in
+/// the units of the sensor. Fans don't report limits so they never alarm.
+///
+/// # Example
+/// ```
+/// use ni_syscfg::{AlarmEvaluator, SensorKind, SessionConfig};
+///
+/// let session = SessionConfig::new().connect().unwrap();
+/// let evaluator = AlarmEvaluator::new()
+///   .margin(SensorKind::Temperature, 5.0)
+///   .margin(SensorKind::Voltage, 0.1);
+///
+/// for alarm in session.health_check_with(&evaluator).unwrap() {
+///   println!("{alarm}");
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AlarmEvaluator {
+    temperature_margin: f64,
+    voltage_margin: f64,
+    power_margin: f64,
+}
+
+impl AlarmEvaluator {
+    /// An evaluator which only alarms on readings past their critical limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Warn when readings of this kind are within `margin` of a critical limit.
+    pub fn margin(mut self, kind: SensorKind, margin: f64) -> Self {
+        let margin = margin.max(0.0);
+        match kind {
+            SensorKind::Temperature => self.temperature_margin = margin,
+            SensorKind::Voltage => self.voltage_margin = margin,
+            SensorKind::Power => self.power_margin = margin,
+            SensorKind::Fan => {}
+        }
+        self
+    }
+
+    fn margin_for(&self, kind: SensorKind) -> f64 {
+        match kind {
+            SensorKind::Temperature => self.temperature_margin,
+            SensorKind::Voltage => self.voltage_margin,
+            SensorKind::Power => self.power_margin,
+            SensorKind::Fan => 0.0,
+        }
+    }
+
+    /// Check all the readings from the resource named `resource`.
+    pub fn evaluate(&self, resource: &str, readings: &SensorReadings) -> Vec<Alarm> {
+        readings
+            .iter()
+            .filter_map(|reading| self.evaluate_reading(resource, reading))
+            .collect()
+    }
+
+    /// Check a single reading, returning the most serious alarm for it.
+    pub fn evaluate_reading(&self, resource: &str, reading: &SensorReading) -> Option<Alarm> {
+        let margin = self.margin_for(reading.kind);
+        let value = reading.value;
+        let upper = reading.upper_critical;
+        let lower = reading.lower_critical;
+
+        let (kind, limit, severity) = if let Some(limit) = upper.filter(|&limit| value > limit) {
+            (AlarmKind::AboveUpperCritical, limit, Severity::Critical)
+        } else if let Some(limit) = lower.filter(|&limit| value < limit) {
+            (AlarmKind::BelowLowerCritical, limit, Severity::Critical)
+        } else if let Some(limit) = upper.filter(|&limit| margin > 0.0 && value >= limit - margin) {
+            (AlarmKind::NearUpperCritical, limit, Severity::Warning)
+        } else if let Some(limit) = lower.filter(|&limit| margin > 0.0 && value <= limit + margin) {
+            (AlarmKind::NearLowerCritical, limit, Severity::Warning)
+        } else {
+            return None;
+        };
+
+        Some(Alarm {
+            resource: resource.to_string(),
+            sensor: reading.name.clone(),
+            sensor_kind: reading.kind,
+            kind,
+            value,
+            limit,
+            severity,
+        })
+    }
+}
+
+impl Session {
+    /// Check the sensors on all chassis and controllers against their critical limits.
+    ///
+    /// An empty list means nothing is out of spec.
+    pub fn health_check(&self) -> Result<Vec<Alarm>> {
+        self.health_check_with(&AlarmEvaluator::new())
+    }
+
+    /// Check the sensors on all chassis and controllers using the margins in `evaluator`.
+    pub fn health_check_with(&self, evaluator: &AlarmEvaluator) -> Result<Vec<Alarm>> {
+        let mut alarms = Vec::new();
+        for (resource, readings) in self.sensor_readings()? {
+            alarms.extend(evaluator.evaluate(&resource, &readings));
+        }
+        Ok(alarms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::reading;
+
+    fn temperature(value: f64) -> SensorReading {
+        SensorReading {
+            lower_critical: Some(0.0),
+            upper_critical: Some(90.0),
+            ..reading(SensorKind::Temperature, "CPU", value)
+        }
+    }
+
+    fn evaluate(
+        evaluator: &AlarmEvaluator,
+        reading: SensorReading,
+    ) -> Option<(AlarmKind, Severity)> {
+        evaluator
+            .evaluate_reading("PXI1Controller", &reading)
+            .map(|alarm| (alarm.kind, alarm.severity))
+    }
+
+    #[test]
+    fn test_critical_limits() {
+        let evaluator = AlarmEvaluator::new();
+        assert_eq!(evaluate(&evaluator, temperature(45.0)), None);
+        assert_eq!(evaluate(&evaluator, temperature(90.0)), None);
+        assert_eq!(
+            evaluate(&evaluator, temperature(95.0)),
+            Some((AlarmKind::AboveUpperCritical, Severity::Critical))
+        );
+        assert_eq!(
+            evaluate(&evaluator, temperature(-5.0)),
+            Some((AlarmKind::BelowLowerCritical, Severity::Critical))
+        );
+    }
+
+    #[test]
+    fn test_margins() {
+        let evaluator = AlarmEvaluator::new().margin(SensorKind::Temperature, 5.0);
+        assert_eq!(
+            evaluate(&evaluator, temperature(86.0)),
+            Some((AlarmKind::NearUpperCritical, Severity::Warning))
+        );
+        assert_eq!(
+            evaluate(&evaluator, temperature(4.0)),
+            Some((AlarmKind::NearLowerCritical, Severity::Warning))
+        );
+        assert_eq!(evaluate(&evaluator, temperature(50.0)), None);
+        assert_eq!(
+            evaluate(&evaluator, temperature(91.0)),
+            Some((AlarmKind::AboveUpperCritical, Severity::Critical))
+        );
+
+        // Margins only apply to their own kind of sensor.
+        let voltage_only = AlarmEvaluator::new().margin(SensorKind::Voltage, 5.0);
+        assert_eq!(evaluate(&voltage_only, temperature(86.0)), None);
+    }
+
+    #[test]
+    fn test_readings_without_limits() {
+        let fan = SensorReading {
+            kind: SensorKind::Fan,
+            name: "Fan 1".to_string(),
+            value: 0.0,
+            nominal: None,
+            lower_critical: None,
+            upper_critical: None,
+        };
+        let evaluator = AlarmEvaluator::new().margin(SensorKind::Fan, 100.0);
+        assert_eq!(evaluate(&evaluator, fan), None);
+    }
+
+    #[test]
+    fn test_evaluate_all_readings() {
+        let readings = SensorReadings {
+            temperature: vec![temperature(95.0), temperature(40.0)],
+            power: vec![SensorReading {
+                kind: SensorKind::Power,
+                name: "PSU".to_string(),
+                value: 410.0,
+                nominal: None,
+                lower_critical: None,
+                upper_critical: Some(400.0),
+            }],
+            ..Default::default()
+        };
+
+        let alarms = AlarmEvaluator::new().evaluate("PXI1Chassis1", &readings);
+        assert_eq!(alarms.len(), 2);
+        assert_eq!(alarms[1].sensor, "PSU");
+        assert_eq!(alarms[1].limit, 400.0);
+        assert_eq!(
+            alarms[0].to_string(),
+            "Critical: PXI1Chassis1 CPU is 95 °C above the upper critical limit of 90 °C"
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Calibration records for hardware resources.
+//!
+//! The API spreads the calibration information across many resource properties.
+//! These are collected into a single [CalibrationInfo] here along with
+//! the methods for writing the calibration metadata.
+
+use std::ffi::{CStr, CString};
+use std::time::{Duration, SystemTime};
+
+use ni_syscfg_sys::*;
+use zeroize::Zeroizing;
+
+use super::HardwareResource;
+use crate::error::{NiSystemConfigurationError, Result};
+use crate::parameters::WriteableParameter;
+
+/// The average length of a month in the Gregorian calendar, used to convert
+/// the recommended calibration interval, which the API provides in months.
+const SECONDS_PER_MONTH: u64 = 2_629_746;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The calibration record of a hardware resource.
+///
+/// Fields are [None] where the device doesn't report that value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrationInfo {
+    /// Whether the device supports self calibration.
+    pub supports_internal: bool,
+    /// When the last self calibration was completed.
+    pub internal_last_time: Option<SystemTime>,
+    /// The device temperature in degrees Celsius at the last self calibration.
+    pub internal_last_temperature: Option<f64>,
+    /// Whether the device supports external calibration.
+    pub supports_external: bool,
+    /// When the last external calibration was completed.
+    pub external_last_time: Option<SystemTime>,
+    /// The device temperature in degrees Celsius at the last external calibration.
+    pub external_last_temperature: Option<f64>,
+    /// The interval between external calibrations recommended by NI.
+    pub recommended_interval: Option<Duration>,
+    /// The date that the next external calibration is due.
+    pub next_due: Option<SystemTime>,
+    /// The comments stored with the last external calibration.
+    pub comments: Option<String>,
+    /// The checksum of the external calibration data.
+    pub external_checksum: Option<String>,
+    /// Whether the calibration metadata can be written to the device.
+    pub supports_calibration_write: bool,
+}
+
+impl CalibrationInfo {
+    /// The time at which the next external calibration is due.
+    ///
+    /// This uses the due date reported by the device where available, otherwise
+    /// the last external calibration plus the recommended interval.
+    pub fn due(&self) -> Option<SystemTime> {
+        self.next_due.or_else(|| {
+            self.external_last_time
+                .zip(self.recommended_interval)
+                .map(|(last, interval)| last + interval)
+        })
+    }
+
+    /// Returns true if the external calibration was due before `now`.
+    ///
+    /// Devices with no due date are never overdue.
+    pub fn is_overdue(&self, now: SystemTime) -> bool {
+        self.due().is_some_and(|due| due < now)
+    }
+
+    /// The whole number of days from `now` until the next external calibration is due.
+    ///
+    /// This is negative when the calibration is overdue and [None] if no due date is known.
+    pub fn days_until_due(&self, now: SystemTime) -> Option<i64> {
+        let due = self.due()?;
+        let seconds = match due.duration_since(now) {
+            Ok(remaining) => remaining.as_secs() as i64,
+            Err(overdue) => -(overdue.duration().as_secs() as i64),
+        };
+        Some(seconds / SECONDS_PER_DAY)
+    }
+}
+
+impl HardwareResource {
+    /// Read the calibration record of the resource.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::SessionConfig;
+    /// use std::time::SystemTime;
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    ///
+    /// for hardware in session.find_hardware(None, None).unwrap() {
+    ///   let calibration = hardware.calibration().unwrap();
+    ///   if calibration.is_overdue(SystemTime::now()) {
+    ///     println!("{} is overdue for calibration", hardware.name().unwrap());
+    ///   }
+    /// }
+    /// ```
+    pub fn calibration(&self) -> Result<CalibrationInfo> {
+        Ok(CalibrationInfo {
+            supports_internal: self.optional_bool(
+                NISysCfgResourceProperty_NISysCfgResourcePropertySupportsInternalCalibration,
+            )?,
+            internal_last_time: self.optional_time(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyInternalCalibrationLastTime,
+            )?,
+            internal_last_temperature: self.optional_parameter::<f64>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyInternalCalibrationLastTemp,
+            )?,
+            supports_external: self.optional_bool(
+                NISysCfgResourceProperty_NISysCfgResourcePropertySupportsExternalCalibration,
+            )?,
+            external_last_time: self.optional_time(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyExternalCalibrationLastTime,
+            )?,
+            external_last_temperature: self.optional_parameter::<f64>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyExternalCalibrationLastTemp,
+            )?,
+            recommended_interval: self
+                .optional_parameter::<i32>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyRecommendedCalibrationInterval,
+                )?
+                .and_then(months_to_duration),
+            next_due: self.optional_time(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyRecommendedNextCalibrationTime,
+            )?,
+            comments: self.optional_parameter::<String>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyCalibrationComments,
+            )?,
+            external_checksum: self.optional_parameter::<String>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyExternalCalibrationChecksum,
+            )?,
+            supports_calibration_write: self.optional_bool(
+                NISysCfgResourceProperty_NISysCfgResourcePropertySupportsCalibrationWrite,
+            )?,
+        })
+    }
+}
+
+impl HardwareResource {
+    /// Write the comments stored with the external calibration.
+    ///
+    /// The current calibration `password` is required to authorise the change.
+    /// Returns true if the change requires a restart of the system.
+    ///
+    /// This returns [NiSystemConfigurationError::CalibrationWriteNotSupported] if the
+    /// device doesn't report `SupportsCalibrationWrite`.
+    pub fn set_calibration_comments(&self, comments: &str, password: &str) -> Result<bool> {
+        self.ensure_calibration_write()?;
+        let password = SecretCString::new(password)?;
+        let comments = CString::new(comments)?;
+        password.as_c_str().write_resource_parameter(
+            self.handle,
+            NISysCfgResourceProperty_NISysCfgResourcePropertyCalibrationCurrentPassword,
+        )?;
+        comments.as_c_str().write_resource_parameter(
+            self.handle,
+            NISysCfgResourceProperty_NISysCfgResourcePropertyCalibrationComments,
+        )?;
+        self.save_changes()
+    }
+
+    /// Change the calibration password from `current_password` to `new_password`.
+    ///
+    /// Returns true if the change requires a restart of the system.
+    ///
+    /// This returns [NiSystemConfigurationError::CalibrationWriteNotSupported] if the
+    /// device doesn't report `SupportsCalibrationWrite`.
+    pub fn change_calibration_password(
+        &self,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<bool> {
+        self.ensure_calibration_write()?;
+        let current_password = SecretCString::new(current_password)?;
+        let new_password = SecretCString::new(new_password)?;
+        current_password.as_c_str().write_resource_parameter(
+            self.handle,
+            NISysCfgResourceProperty_NISysCfgResourcePropertyCalibrationCurrentPassword,
+        )?;
+        new_password.as_c_str().write_resource_parameter(
+            self.handle,
+            NISysCfgResourceProperty_NISysCfgResourcePropertyCalibrationNewPassword,
+        )?;
+        self.save_changes()
+    }
+
+    fn ensure_calibration_write(&self) -> Result<()> {
+        if self.optional_bool(
+            NISysCfgResourceProperty_NISysCfgResourcePropertySupportsCalibrationWrite,
+        )? {
+            Ok(())
+        } else {
+            Err(NiSystemConfigurationError::CalibrationWriteNotSupported)
+        }
+    }
+}
+
+/// A null terminated copy of a secret for passing to the API which is zeroed when dropped.
+struct SecretCString(Zeroizing<Vec<u8>>);
+
+impl SecretCString {
+    fn new(secret: &str) -> Result<Self> {
+        // Check first as a NulError would carry a copy of the secret with it.
+        if secret.contains('\0') {
+            return Err(NiSystemConfigurationError::NulInSecret);
+        }
+        let mut bytes = Zeroizing::new(Vec::with_capacity(secret.len() + 1));
+        bytes.extend_from_slice(secret.as_bytes());
+        bytes.push(0);
+        Ok(Self(bytes))
+    }
+
+    fn as_c_str(&self) -> &CStr {
+        // Safe to unwrap as we have checked for interior nulls and added the terminator.
+        CStr::from_bytes_with_nul(&self.0).unwrap()
+    }
+}
+
+/// The API reports the interval in whole months, with zero or less meaning no recommendation.
+fn months_to_duration(months: i32) -> Option<Duration> {
+    if months > 0 {
+        Some(Duration::from_secs(months as u64 * SECONDS_PER_MONTH))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    const DAY: Duration = Duration::from_secs(SECONDS_PER_DAY as u64);
+
+    fn info() -> CalibrationInfo {
+        CalibrationInfo {
+            supports_internal: true,
+            internal_last_time: None,
+            internal_last_temperature: None,
+            supports_external: true,
+            external_last_time: None,
+            external_last_temperature: None,
+            recommended_interval: None,
+            next_due: None,
+            comments: None,
+            external_checksum: None,
+            supports_calibration_write: false,
+        }
+    }
+
+    #[test]
+    fn test_secret_cstring() {
+        let secret = SecretCString::new("password").unwrap();
+        assert_eq!(secret.as_c_str().to_str().unwrap(), "password");
+    }
+
+    #[test]
+    fn test_secret_cstring_rejects_nul() {
+        assert!(matches!(
+            SecretCString::new("pass\0word"),
+            Err(NiSystemConfigurationError::NulInSecret)
+        ));
+    }
+
+    #[test]
+    fn test_months_to_duration() {
+        assert_eq!(months_to_duration(0), None);
+        assert_eq!(months_to_duration(-1), None);
+        assert_eq!(
+            months_to_duration(12),
+            Some(Duration::from_secs(365 * 86_400 + 20_952))
+        );
+    }
+
+    #[test]
+    fn test_no_due_date_is_not_overdue() {
+        let info = info();
+        let now = SystemTime::now();
+        assert!(!info.is_overdue(now));
+        assert_eq!(info.days_until_due(now), None);
+    }
+
+    #[test]
+    fn test_next_due_is_used() {
+        let now = UNIX_EPOCH + 1000 * DAY;
+        let mut info = info();
+        info.next_due = Some(now + 10 * DAY);
+        assert!(!info.is_overdue(now));
+        assert_eq!(info.days_until_due(now), Some(10));
+    }
+
+    #[test]
+    fn test_overdue_from_next_due() {
+        let now = UNIX_EPOCH + 1000 * DAY;
+        let mut info = info();
+        info.next_due = Some(now - 3 * DAY);
+        assert!(info.is_overdue(now));
+        assert_eq!(info.days_until_due(now), Some(-3));
+    }
+
+    #[test]
+    fn test_due_from_last_calibration_and_interval() {
+        let now = UNIX_EPOCH + 1000 * DAY;
+        let mut info = info();
+        info.external_last_time = Some(now - 400 * DAY);
+        info.recommended_interval = Some(365 * DAY);
+        assert!(info.is_overdue(now));
+        assert_eq!(info.days_until_due(now), Some(-35));
+    }
+}
@@ -0,0 +1,131 @@
+//! Processor details and load of controllers and real-time targets.
+
+use ni_syscfg_sys::*;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::parameters::ReadableParameter;
+use crate::session::Session;
+
+/// The processor in a controller.
+///
+/// Values which the resource doesn't report are [None].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CpuInfo {
+    pub model_name: Option<String>,
+    pub stepping_revision: Option<i32>,
+    /// The number of physical cores.
+    pub cores: Option<i32>,
+    /// The number of logical processors, which is more than the cores with hyperthreading.
+    pub logical_processors: Option<i32>,
+}
+
+/// The load on a single logical processor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CpuLoad {
+    /// The index of the logical processor.
+    pub processor: u32,
+    /// The total load as a percentage, including time handling interrupts.
+    pub total_load: u32,
+    /// The load from handling interrupts as a percentage.
+    pub interrupt_load: u32,
+    /// The current clock speed in MHz.
+    pub speed_mhz: u32,
+}
+
+impl CpuLoad {
+    /// The load from everything other than interrupts as a percentage.
+    pub fn thread_load(&self) -> u32 {
+        self.total_load.saturating_sub(self.interrupt_load)
+    }
+}
+
+impl HardwareResource {
+    /// Details of the processor, for controllers and real-time targets.
+    pub fn cpu_info(&self) -> Result<CpuInfo> {
+        Ok(CpuInfo {
+            model_name: self.optional_parameter::<String>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyCpuModelName,
+            )?,
+            stepping_revision: self.optional_parameter::<i32>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyCpuSteppingRevision,
+            )?,
+            cores: self.optional_parameter::<i32>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfCpuCores,
+            )?,
+            logical_processors: self.optional_parameter::<i32>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfCpuLogicalProcessors,
+            )?,
+        })
+    }
+
+    /// The current load on each logical processor.
+    ///
+    /// This is empty for resources which don't report their processor load.
+    pub fn cpu_loads(&self) -> Result<Vec<CpuLoad>> {
+        let count = self
+            .optional_parameter::<i32>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfCpuLogicalProcessors,
+            )?
+            .unwrap_or(0);
+
+        (0..count.max(0) as u32)
+            .map(|processor| {
+                let read = |id| u32::read_resource_indexed_parameter(self.handle, id, processor);
+                Ok(CpuLoad {
+                    processor,
+                    total_load: read(NISysCfgIndexedProperty_NISysCfgIndexedPropertyCpuTotalLoad)?,
+                    interrupt_load: read(
+                        NISysCfgIndexedProperty_NISysCfgIndexedPropertyCpuInterruptLoad,
+                    )?,
+                    speed_mhz: read(NISysCfgIndexedProperty_NISysCfgIndexedPropertyCpuSpeed)?,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Session {
+    /// Details of the processor of the system.
+    pub fn cpu_info(&self) -> Result<CpuInfo> {
+        self.system_resource()?.cpu_info()
+    }
+
+    /// The current load on each logical processor of the system.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::SessionConfig;
+    ///
+    /// let session = SessionConfig::new().target("192.168.0.10").connect().unwrap();
+    ///
+    /// for load in session.cpu_loads().unwrap() {
+    ///   println!("CPU {}: {}% ({}% interrupts)", load.processor, load.total_load, load.interrupt_load);
+    /// }
+    /// ```
+    pub fn cpu_loads(&self) -> Result<Vec<CpuLoad>> {
+        self.system_resource()?.cpu_loads()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thread_load() {
+        let load = CpuLoad {
+            processor: 0,
+            total_load: 60,
+            interrupt_load: 15,
+            speed_mhz: 2400,
+        };
+        assert_eq!(load.thread_load(), 45);
+
+        let inconsistent = CpuLoad {
+            interrupt_load: 70,
+            ..load
+        };
+        assert_eq!(inconsistent.thread_load(), 0);
+    }
+}
@@ -0,0 +1,125 @@
+//! The experts (drivers) which manage a hardware resource.
+
+use ni_syscfg_sys::*;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::experts::ExpertType;
+use crate::parameters::ReadableParameter;
+
+/// How a single expert identifies a hardware resource.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpertInfo {
+    pub expert_type: ExpertType,
+    /// The name the expert uses to address the resource, e.g. `Dev1` for DAQmx or `RIO0` for RIO.
+    pub resource_name: String,
+    /// The user defined alias for the resource in this expert.
+    pub user_alias: String,
+}
+
+impl HardwareResource {
+    /// The experts which manage this resource along with the names each uses for it.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::SessionConfig;
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    ///
+    /// for hardware in session.find_hardware(None, None).unwrap() {
+    ///   for expert in hardware.experts().unwrap() {
+    ///     println!("{:?}: {} ({})", expert.expert_type, expert.resource_name, expert.user_alias);
+    ///   }
+    /// }
+    /// ```
+    pub fn experts(&self) -> Result<Vec<ExpertInfo>> {
+        let count = self
+            .optional_parameter::<i32>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfExperts,
+            )?
+            .unwrap_or(0);
+
+        (0..count.max(0) as u32)
+            .map(|index| self.expert_at(index))
+            .collect()
+    }
+
+    /// The identity of the resource for a specific expert, or [None] if that expert doesn't manage it.
+    pub fn expert(&self, expert_type: &ExpertType) -> Result<Option<ExpertInfo>> {
+        Ok(find_expert(self.experts()?, expert_type))
+    }
+
+    /// The user alias of the resource for a specific expert.
+    ///
+    /// This is the equivalent of [HardwareResource::name] for resources managed by more than one expert.
+    pub fn name_for_expert(&self, expert_type: &ExpertType) -> Result<Option<String>> {
+        Ok(self.expert(expert_type)?.map(|expert| expert.user_alias))
+    }
+
+    fn expert_at(&self, index: u32) -> Result<ExpertInfo> {
+        let read = |id| String::read_resource_indexed_parameter(self.handle, id, index);
+        Ok(ExpertInfo::new(
+            &read(NISysCfgIndexedProperty_NISysCfgIndexedPropertyExpertName)?,
+            read(NISysCfgIndexedProperty_NISysCfgIndexedPropertyExpertResourceName)?,
+            read(NISysCfgIndexedProperty_NISysCfgIndexedPropertyExpertUserAlias)?,
+        ))
+    }
+}
+
+impl ExpertInfo {
+    /// Build the info from the strings the API reports, mapping the expert name to its type.
+    fn new(expert_name: &str, resource_name: String, user_alias: String) -> Self {
+        Self {
+            expert_type: ExpertType::from(expert_name),
+            resource_name,
+            user_alias,
+        }
+    }
+}
+
+fn find_expert(experts: Vec<ExpertInfo>, expert_type: &ExpertType) -> Option<ExpertInfo> {
+    experts
+        .into_iter()
+        .find(|expert| &expert.expert_type == expert_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn experts() -> Vec<ExpertInfo> {
+        vec![
+            ExpertInfo::new("daqmx", "Dev1".to_string(), "Bench DAQ".to_string()),
+            ExpertInfo::new("ni-rio", "RIO0".to_string(), "FPGA".to_string()),
+            ExpertInfo::new("ni-future", "Fut0".to_string(), String::new()),
+        ]
+    }
+
+    #[test]
+    fn test_expert_names_map_to_types() {
+        let types: Vec<ExpertType> = experts()
+            .into_iter()
+            .map(|expert| expert.expert_type)
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ExpertType::NiDaqmx,
+                ExpertType::NiRio,
+                ExpertType::Unknown("ni-future".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_expert() {
+        let rio = find_expert(experts(), &ExpertType::NiRio).unwrap();
+        assert_eq!(rio.resource_name, "RIO0");
+        assert_eq!(rio.user_alias, "FPGA");
+
+        let unknown = find_expert(experts(), &ExpertType::Unknown("ni-future".to_string()));
+        assert_eq!(unknown.unwrap().resource_name, "Fut0");
+
+        assert_eq!(find_expert(experts(), &ExpertType::NiScope), None);
+    }
+}
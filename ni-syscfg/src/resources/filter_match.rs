@@ -0,0 +1,167 @@
+//! Checking filter properties against a resource on the client side.
+
+use ni_syscfg_sys::*;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::hardware_filter::FilterProperty;
+use crate::parameters::{ApiBool, BusType};
+
+impl HardwareResource {
+    /// Returns true if the resource has the property value, matching how the native filter
+    /// treats it. Resources without the property don't match.
+    pub(crate) fn matches_property(&self, property: &FilterProperty) -> Result<bool> {
+        use FilterProperty::*;
+        Ok(match property {
+            IsDevice(value) => self.has_bool_property(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyIsDevice,
+                *value,
+            )?,
+            IsChassis(value) => self.has_bool_property(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyIsChassis,
+                *value,
+            )?,
+            ServiceType(service) => self.provides_service(*service)?,
+            ConnectsToBusType(bus) => {
+                self.optional_parameter::<BusType>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyConnectsToBusType,
+                )? == Some(*bus)
+            }
+            ConnectsToLinkName(name) => {
+                self.optional_parameter::<String>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyConnectsToLinkName,
+                )?
+                .as_ref()
+                    == Some(name)
+            }
+            ProvidesBusType(bus) => {
+                self.optional_parameter::<BusType>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyProvidesBusType,
+                )? == Some(*bus)
+            }
+            VendorId(id) => {
+                self.optional_parameter::<u32>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyVendorId,
+                )? == Some(*id)
+            }
+            ProductId(id) => {
+                self.optional_parameter::<u32>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyProductId,
+                )? == Some(*id)
+            }
+            SerialNumber(serial) => {
+                self.optional_parameter::<String>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertySerialNumber,
+                )?
+                .as_ref()
+                    == Some(serial)
+            }
+            IsNiProduct(value) => self.has_bool_property(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyIsNIProduct,
+                *value,
+            )?,
+            IsSimulated(value) => self.has_bool_property(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyIsSimulated,
+                *value,
+            )?,
+            SlotNumber(slot) => self.slot_number()? == Some(*slot),
+            HasDriver(installed) => {
+                let expected = if *installed {
+                    NISysCfgHasDriverType_NISysCfgHasDriverTypeInstalled
+                } else {
+                    NISysCfgHasDriverType_NISysCfgHasDriverTypeNotInstalled
+                };
+                self.optional_parameter::<i32>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyHasDriver,
+                )? == Some(expected)
+            }
+            IsPresent(present) => {
+                let expected = if *present {
+                    NISysCfgIsPresentType_NISysCfgIsPresentTypePresent
+                } else {
+                    NISysCfgIsPresentType_NISysCfgIsPresentTypeNotPresent
+                };
+                self.optional_parameter::<i32>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyIsPresent,
+                )? == Some(expected)
+            }
+            SupportsCalibration(value) => {
+                let support = calibration_support(
+                    self.bool_property(
+                        NISysCfgResourceProperty_NISysCfgResourcePropertySupportsInternalCalibration,
+                    )?,
+                    self.bool_property(
+                        NISysCfgResourceProperty_NISysCfgResourcePropertySupportsExternalCalibration,
+                    )?,
+                );
+                matches_bool(support, *value)
+            }
+            SupportsFirmwareUpdate(value) => self.has_bool_property(
+                NISysCfgResourceProperty_NISysCfgResourcePropertySupportsFirmwareUpdate,
+                *value,
+            )?,
+            ProvidesLinkName(name) => {
+                self.optional_parameter::<String>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyProvidesLinkName,
+                )?
+                .as_ref()
+                    == Some(name)
+            }
+            ExpertName(expert) => self.expert(expert)?.is_some(),
+            ResourceName(name) => self
+                .experts()?
+                .iter()
+                .any(|expert| &expert.resource_name == name),
+            UserAlias(alias) => self
+                .experts()?
+                .iter()
+                .any(|expert| &expert.user_alias == alias),
+        })
+    }
+
+    fn has_bool_property(&self, id: NISysCfgResourceProperty, expected: bool) -> Result<bool> {
+        Ok(matches_bool(self.bool_property(id)?, expected))
+    }
+
+    /// Read a boolean property, keeping a missing property as [None] so it doesn't match.
+    fn bool_property(&self, id: NISysCfgResourceProperty) -> Result<Option<bool>> {
+        Ok(self.optional_parameter::<ApiBool>(id)?.map(bool::from))
+    }
+}
+
+/// Boolean properties only match resources which have the property.
+fn matches_bool(actual: Option<bool>, expected: bool) -> bool {
+    actual == Some(expected)
+}
+
+/// Whether a resource supports either kind of calibration, or [None] if it reports neither.
+fn calibration_support(internal: Option<bool>, external: Option<bool>) -> Option<bool> {
+    match (internal, external) {
+        (None, None) => None,
+        _ => Some(internal == Some(true) || external == Some(true)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_bool_properties_dont_match() {
+        assert!(matches_bool(Some(false), false));
+        assert!(matches_bool(Some(true), true));
+        assert!(!matches_bool(Some(true), false));
+        assert!(!matches_bool(None, false));
+        assert!(!matches_bool(None, true));
+        assert!(!matches_bool(calibration_support(None, None), false));
+    }
+
+    #[test]
+    fn test_calibration_support() {
+        assert_eq!(calibration_support(Some(true), None), Some(true));
+        assert_eq!(calibration_support(None, Some(true)), Some(true));
+        assert_eq!(calibration_support(Some(false), None), Some(false));
+        assert_eq!(calibration_support(Some(false), Some(false)), Some(false));
+        assert_eq!(calibration_support(Some(false), Some(true)), Some(true));
+    }
+}
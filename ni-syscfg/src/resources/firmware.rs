@@ -0,0 +1,418 @@
+//! Firmware updates for hardware resources.
+//!
+//! Updates can either block until the device has finished or be started in the background
+//! and then polled through the returned [FirmwareUpdate].
+
+use std::ffi::{c_char, CString};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use ni_syscfg_sys::*;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use super::HardwareResource;
+use crate::error::{api_status, NiSystemConfigurationError, Result};
+use crate::handles::free_detailed_string;
+use crate::parameters::{ApiBool, ValueEnum};
+
+/// The state of the firmware on a device.
+///
+/// The negative values in the API represent an update which is still in progress.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive)]
+pub enum FirmwareStatus {
+    ReadyPendingAutoRestart = NISysCfgFirmwareStatus_NISysCfgFirmwareReadyPendingAutoRestart,
+    VerifyingNewImage = NISysCfgFirmwareStatus_NISysCfgFirmwareVerifyingNewImage,
+    WritingFlashingNewImage = NISysCfgFirmwareStatus_NISysCfgFirmwareWritingFlashingNewImage,
+    UpdateModeWaitingForImage = NISysCfgFirmwareStatus_NISysCfgFirmwareUpdateModeWaitingForImage,
+    CorruptCannotRun = NISysCfgFirmwareStatus_NISysCfgFirmwareCorruptCannotRun,
+    NoneInstalled = NISysCfgFirmwareStatus_NISysCfgFirmwareNoneInstalled,
+    InstalledNormalOperation = NISysCfgFirmwareStatus_NISysCfgFirmwareInstalledNormalOperation,
+    ReadyPendingUserRestart = NISysCfgFirmwareStatus_NISysCfgFirmwareReadyPendingUserRestart,
+    ReadyPendingUserAction = NISysCfgFirmwareStatus_NISysCfgFirmwareReadyPendingUserAction,
+    UpdateAttemptFailed = NISysCfgFirmwareStatus_NISysCfgFirmwareUpdateAttemptFailed,
+}
+
+impl ValueEnum for FirmwareStatus {}
+
+impl FirmwareStatus {
+    fn from_api(value: NISysCfgFirmwareStatus) -> Result<Self> {
+        Self::from_i32(value).ok_or(NiSystemConfigurationError::UnexpectedEnumValue(value))
+    }
+
+    /// Returns true once the device has finished any operation on the firmware.
+    pub fn is_terminal(&self) -> bool {
+        (*self as i32) >= 0
+    }
+
+    /// Returns true if the last firmware operation left the device with usable firmware.
+    ///
+    /// A restart or user action may still be required before the new firmware is running.
+    pub fn is_success(&self) -> bool {
+        matches!(
+            self,
+            Self::InstalledNormalOperation
+                | Self::ReadyPendingUserRestart
+                | Self::ReadyPendingUserAction
+        )
+    }
+}
+
+/// Options for how a firmware update is applied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FirmwareUpdateOptions {
+    auto_stop_tasks: bool,
+    always_overwrite: bool,
+}
+
+impl FirmwareUpdateOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop any tasks running on the device before updating. Otherwise the update fails if the device is in use.
+    pub fn auto_stop_tasks(mut self, auto_stop_tasks: bool) -> Self {
+        self.auto_stop_tasks = auto_stop_tasks;
+        self
+    }
+
+    /// Write the firmware even if the device already has the same version.
+    pub fn always_overwrite(mut self, always_overwrite: bool) -> Self {
+        self.always_overwrite = always_overwrite;
+        self
+    }
+}
+
+/// A snapshot of the progress of a firmware operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FirmwareProgress {
+    /// The percentage of the operation completed.
+    pub percent_complete: i32,
+    pub status: FirmwareStatus,
+}
+
+/// A firmware operation which has been started without waiting for it to finish.
+///
+/// Use [FirmwareUpdate::poll] to check on the progress or [FirmwareUpdate::wait_timeout] to block until it finishes.
+pub struct FirmwareUpdate<'a> {
+    resource: &'a HardwareResource,
+    initial_status: FirmwareStatus,
+}
+
+impl<'a> FirmwareUpdate<'a> {
+    /// The status reported by the device when the operation was started.
+    pub fn initial_status(&self) -> FirmwareStatus {
+        self.initial_status
+    }
+
+    /// Check the current progress of the operation.
+    pub fn poll(&self) -> Result<FirmwareProgress> {
+        self.resource.firmware_status()
+    }
+
+    /// Poll the device every `poll_interval` until the status is terminal and return the final status.
+    ///
+    /// This will block for as long as the device reports the operation is in progress, which can be
+    /// forever if it is waiting for someone to act. Prefer [FirmwareUpdate::wait_timeout].
+    pub fn wait(&self, poll_interval: Duration) -> Result<FirmwareStatus> {
+        wait_until_terminal(|| self.poll(), poll_interval, None)
+    }
+
+    /// Poll the device every `poll_interval` until the status is terminal and return the final status.
+    ///
+    /// Returns [NiSystemConfigurationError::FirmwareTimeout] with the last status if the operation
+    /// hasn't finished within `timeout`.
+    pub fn wait_timeout(
+        &self,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<FirmwareStatus> {
+        wait_until_terminal(
+            || self.poll(),
+            poll_interval,
+            Some(Instant::now() + timeout),
+        )
+    }
+}
+
+fn wait_until_terminal(
+    mut poll: impl FnMut() -> Result<FirmwareProgress>,
+    poll_interval: Duration,
+    deadline: Option<Instant>,
+) -> Result<FirmwareStatus> {
+    loop {
+        let progress = poll()?;
+        if progress.status.is_terminal() {
+            return Ok(progress.status);
+        }
+        let sleep = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err(NiSystemConfigurationError::FirmwareTimeout(progress.status));
+                }
+                poll_interval.min(remaining)
+            }
+            None => poll_interval,
+        };
+        std::thread::sleep(sleep);
+    }
+}
+
+impl HardwareResource {
+    /// Update the firmware on the device from the `firmware_file` and wait for the update to finish.
+    ///
+    /// Wraps `NISysCfgUpgradeFirmwareFromFile`.
+    pub fn upgrade_firmware_from_file(
+        &self,
+        firmware_file: impl AsRef<Path>,
+        options: FirmwareUpdateOptions,
+    ) -> Result<FirmwareStatus> {
+        self.firmware_from_file(firmware_file, options, true)
+    }
+
+    /// Start updating the firmware on the device from the `firmware_file` without waiting for it to finish.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::{SessionConfig, FirmwareUpdateOptions};
+    /// use std::path::Path;
+    /// use std::time::Duration;
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    ///
+    /// for hardware in session.find_hardware(None, None).unwrap() {
+    ///   let update = hardware
+    ///     .start_firmware_upgrade_from_file(Path::new("firmware.cfg"), FirmwareUpdateOptions::new())
+    ///     .unwrap();
+    ///   let status = update
+    ///     .wait_timeout(Duration::from_secs(1), Duration::from_secs(600))
+    ///     .unwrap();
+    ///   println!("Firmware update finished with {status:?}");
+    /// }
+    /// ```
+    pub fn start_firmware_upgrade_from_file(
+        &self,
+        firmware_file: impl AsRef<Path>,
+        options: FirmwareUpdateOptions,
+    ) -> Result<FirmwareUpdate<'_>> {
+        let initial_status = self.firmware_from_file(firmware_file, options, false)?;
+        Ok(FirmwareUpdate {
+            resource: self,
+            initial_status,
+        })
+    }
+
+    /// Update the firmware on the device to a `version` the driver has available and wait for the update to finish.
+    ///
+    /// Wraps `NISysCfgUpgradeFirmwareVersion`.
+    pub fn upgrade_firmware_version(
+        &self,
+        version: &str,
+        options: FirmwareUpdateOptions,
+    ) -> Result<FirmwareStatus> {
+        self.firmware_version(version, options, true)
+    }
+
+    /// Start updating the firmware on the device to a `version` the driver has available without waiting for it to finish.
+    pub fn start_firmware_upgrade_version(
+        &self,
+        version: &str,
+        options: FirmwareUpdateOptions,
+    ) -> Result<FirmwareUpdate<'_>> {
+        let initial_status = self.firmware_version(version, options, false)?;
+        Ok(FirmwareUpdate {
+            resource: self,
+            initial_status,
+        })
+    }
+
+    /// Erase the firmware on the device.
+    ///
+    /// Wraps `NISysCfgEraseFirmware`.
+    pub fn erase_firmware(&self, auto_stop_tasks: bool) -> Result<FirmwareStatus> {
+        let mut status: NISysCfgFirmwareStatus = 0;
+        let mut detailed_result: *mut c_char = std::ptr::null_mut();
+        let result = unsafe {
+            NISysCfgEraseFirmware(
+                self.handle,
+                ApiBool::from(auto_stop_tasks) as NISysCfgBool,
+                &mut status,
+                &mut detailed_result,
+            )
+        };
+        free_detailed_string(detailed_result);
+        api_status(result)?;
+        FirmwareStatus::from_api(status)
+    }
+
+    /// Check the status of the firmware on the device, including the progress of any update.
+    ///
+    /// Wraps `NISysCfgCheckFirmwareStatus`.
+    pub fn firmware_status(&self) -> Result<FirmwareProgress> {
+        let mut percent_complete = 0;
+        let mut status: NISysCfgFirmwareStatus = 0;
+        let mut detailed_result: *mut c_char = std::ptr::null_mut();
+        let result = unsafe {
+            NISysCfgCheckFirmwareStatus(
+                self.handle,
+                &mut percent_complete,
+                &mut status,
+                &mut detailed_result,
+            )
+        };
+        free_detailed_string(detailed_result);
+        api_status(result)?;
+        Ok(FirmwareProgress {
+            percent_complete,
+            status: FirmwareStatus::from_api(status)?,
+        })
+    }
+
+    fn firmware_from_file(
+        &self,
+        firmware_file: impl AsRef<Path>,
+        options: FirmwareUpdateOptions,
+        wait: bool,
+    ) -> Result<FirmwareStatus> {
+        let path = CString::new(
+            firmware_file
+                .as_ref()
+                .as_os_str()
+                .to_string_lossy()
+                .as_ref(),
+        )?;
+        let mut status: NISysCfgFirmwareStatus = 0;
+        let mut detailed_result: *mut c_char = std::ptr::null_mut();
+        let result = unsafe {
+            NISysCfgUpgradeFirmwareFromFile(
+                self.handle,
+                path.as_ptr(),
+                ApiBool::from(options.auto_stop_tasks) as NISysCfgBool,
+                ApiBool::from(options.always_overwrite) as NISysCfgBool,
+                ApiBool::from(wait) as NISysCfgBool,
+                &mut status,
+                &mut detailed_result,
+            )
+        };
+        free_detailed_string(detailed_result);
+        api_status(result)?;
+        FirmwareStatus::from_api(status)
+    }
+
+    fn firmware_version(
+        &self,
+        version: &str,
+        options: FirmwareUpdateOptions,
+        wait: bool,
+    ) -> Result<FirmwareStatus> {
+        let version = CString::new(version)?;
+        let mut status: NISysCfgFirmwareStatus = 0;
+        let mut detailed_result: *mut c_char = std::ptr::null_mut();
+        let result = unsafe {
+            NISysCfgUpgradeFirmwareVersion(
+                self.handle,
+                version.as_ptr(),
+                ApiBool::from(options.auto_stop_tasks) as NISysCfgBool,
+                ApiBool::from(options.always_overwrite) as NISysCfgBool,
+                ApiBool::from(wait) as NISysCfgBool,
+                &mut status,
+                &mut detailed_result,
+            )
+        };
+        free_detailed_string(detailed_result);
+        api_status(result)?;
+        FirmwareStatus::from_api(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_from_api() {
+        assert_eq!(
+            FirmwareStatus::from_api(-4).unwrap(),
+            FirmwareStatus::ReadyPendingAutoRestart
+        );
+        assert_eq!(
+            FirmwareStatus::from_api(5).unwrap(),
+            FirmwareStatus::UpdateAttemptFailed
+        );
+        assert!(FirmwareStatus::from_api(6).is_err());
+    }
+
+    #[test]
+    fn test_in_progress_states_are_not_terminal() {
+        assert!(!FirmwareStatus::ReadyPendingAutoRestart.is_terminal());
+        assert!(!FirmwareStatus::VerifyingNewImage.is_terminal());
+        assert!(!FirmwareStatus::WritingFlashingNewImage.is_terminal());
+        assert!(!FirmwareStatus::UpdateModeWaitingForImage.is_terminal());
+    }
+
+    #[test]
+    fn test_terminal_states() {
+        assert!(FirmwareStatus::InstalledNormalOperation.is_terminal());
+        assert!(FirmwareStatus::UpdateAttemptFailed.is_terminal());
+        assert!(!FirmwareStatus::UpdateAttemptFailed.is_success());
+        assert!(!FirmwareStatus::CorruptCannotRun.is_success());
+        assert!(FirmwareStatus::ReadyPendingUserRestart.is_success());
+    }
+
+    fn progress(status: FirmwareStatus) -> Result<FirmwareProgress> {
+        Ok(FirmwareProgress {
+            percent_complete: 0,
+            status,
+        })
+    }
+
+    #[test]
+    fn test_wait_until_terminal() {
+        let mut statuses = vec![
+            FirmwareStatus::InstalledNormalOperation,
+            FirmwareStatus::WritingFlashingNewImage,
+            FirmwareStatus::VerifyingNewImage,
+        ];
+        let status = wait_until_terminal(
+            || progress(statuses.pop().unwrap()),
+            Duration::ZERO,
+            Some(Instant::now() + Duration::from_secs(10)),
+        )
+        .unwrap();
+        assert_eq!(status, FirmwareStatus::InstalledNormalOperation);
+        assert!(statuses.is_empty());
+    }
+
+    #[test]
+    fn test_wait_times_out() {
+        let result = wait_until_terminal(
+            || progress(FirmwareStatus::UpdateModeWaitingForImage),
+            Duration::from_millis(5),
+            Some(Instant::now() + Duration::from_millis(20)),
+        );
+        assert!(matches!(
+            result,
+            Err(NiSystemConfigurationError::FirmwareTimeout(
+                FirmwareStatus::UpdateModeWaitingForImage
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_update_options() {
+        let options = FirmwareUpdateOptions::new()
+            .auto_stop_tasks(true)
+            .always_overwrite(true);
+        assert!(options.auto_stop_tasks);
+        assert!(options.always_overwrite);
+        assert_eq!(
+            FirmwareUpdateOptions::new(),
+            FirmwareUpdateOptions {
+                auto_stop_tasks: false,
+                always_overwrite: false
+            }
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Matching local firmware files against the file pattern a device publishes.
+
+use std::path::{Path, PathBuf};
+
+use ni_syscfg_sys::*;
+
+use super::{FirmwareVersion, HardwareResource};
+use crate::error::Result;
+
+/// The pattern a device publishes for the names of its firmware files.
+///
+/// This is a file name pattern where `*` matches any run of characters and `?` matches a
+/// single character. Multiple patterns can be separated by `;`. Matching ignores case.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FirmwareFilePattern {
+    patterns: Vec<String>,
+}
+
+impl From<&str> for FirmwareFilePattern {
+    fn from(pattern: &str) -> Self {
+        Self {
+            patterns: pattern
+                .split(';')
+                .map(|part| part.trim().to_lowercase())
+                .filter(|part| !part.is_empty())
+                .collect(),
+        }
+    }
+}
+
+impl FirmwareFilePattern {
+    /// Returns true if the file name matches the pattern.
+    pub fn matches(&self, file_name: &str) -> bool {
+        let file_name: Vec<char> = file_name.to_lowercase().chars().collect();
+        self.patterns.iter().any(|pattern| {
+            let pattern: Vec<char> = pattern.chars().collect();
+            wildcard_match(&pattern, &file_name)
+        })
+    }
+
+    /// Find the files in `directory` which match the pattern, sorted by the version in their name.
+    ///
+    /// Files without a version in their name sort first, so the newest version is last.
+    pub fn find_in(&self, directory: &Path) -> Result<Vec<FirmwareFile>> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(directory)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if self.matches(&name) {
+                files.push(FirmwareFile {
+                    version: version_in_file_name(&name),
+                    path: entry.path(),
+                });
+            }
+        }
+        files.sort_by(|a, b| a.version.cmp(&b.version).then_with(|| a.path.cmp(&b.path)));
+        Ok(files)
+    }
+}
+
+/// A firmware file found on disk with the version taken from its name.
+///
+/// This can be passed directly to [HardwareResource::upgrade_firmware_from_file].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FirmwareFile {
+    pub path: PathBuf,
+    pub version: Option<FirmwareVersion>,
+}
+
+impl AsRef<Path> for FirmwareFile {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl HardwareResource {
+    /// The pattern for firmware file names which this device accepts, if it supports firmware updates from file.
+    pub fn firmware_file_pattern(&self) -> Result<Option<FirmwareFilePattern>> {
+        Ok(self
+            .optional_parameter::<String>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyFirmwareFilePattern,
+            )?
+            .filter(|pattern| !pattern.is_empty())
+            .map(|pattern| FirmwareFilePattern::from(pattern.as_str())))
+    }
+
+    /// Find the firmware files in `directory` which match this device, sorted by the version in their name.
+    ///
+    /// Returns an empty list if the device doesn't publish a firmware file pattern.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::{SessionConfig, FirmwareUpdateOptions};
+    /// use std::path::Path;
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    ///
+    /// for hardware in session.find_hardware(None, None).unwrap() {
+    ///   let files = hardware.matching_firmware_files(Path::new("firmware")).unwrap();
+    ///   if let Some(latest) = files.last() {
+    ///     hardware.upgrade_firmware_from_file(latest, FirmwareUpdateOptions::new()).unwrap();
+    ///   }
+    /// }
+    /// ```
+    pub fn matching_firmware_files(&self, directory: &Path) -> Result<Vec<FirmwareFile>> {
+        match self.firmware_file_pattern()? {
+            Some(pattern) => pattern.find_in(directory),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Match `text` against a `pattern` containing `*` and `?` wildcards.
+fn wildcard_match(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // The position of the last `*` in the pattern and the text position it was tried against.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, star_t)) => {
+                    p = star + 1;
+                    t = star_t + 1;
+                    backtrack = Some((star, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Find the version embedded in a file name such as `cDAQ-9189_1.2.0f3.cfg`.
+///
+/// This takes the last run of dot separated numbers, with an optional stage and build suffix.
+/// A single number without a dot is not treated as a version as it is normally part of the model.
+fn version_in_file_name(file_name: &str) -> Option<FirmwareVersion> {
+    let chars: Vec<char> = file_name.chars().collect();
+    let mut found = None;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let starts_number = chars[index].is_ascii_digit()
+            && (index == 0 || !(chars[index - 1].is_ascii_digit() || chars[index - 1] == '.'));
+        if !starts_number {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') {
+            index += 1;
+        }
+        // A trailing dot is the start of the extension rather than part of the version.
+        let mut end = index;
+        while chars[end - 1] == '.' {
+            end -= 1;
+        }
+        if end == index
+            && index + 1 < chars.len()
+            && chars[index].is_ascii_alphabetic()
+            && chars[index + 1].is_ascii_digit()
+        {
+            index += 1;
+            while index < chars.len() && chars[index].is_ascii_digit() {
+                index += 1;
+            }
+            end = index;
+        }
+
+        let candidate: String = chars[start..end].iter().collect();
+        if candidate.contains('.') {
+            found = Some(candidate);
+        }
+    }
+
+    found.map(|version| FirmwareVersion::from(version.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        FirmwareFilePattern::from(pattern).matches(name)
+    }
+
+    #[test]
+    fn test_wildcard_matching() {
+        assert!(matches("*.cfg", "firmware.cfg"));
+        assert!(matches("cDAQ-9189_*.cfg", "cdaq-9189_1.2.0.cfg"));
+        assert!(matches("fw?.bin", "fw1.bin"));
+        assert!(!matches("fw?.bin", "fw12.bin"));
+        assert!(!matches("*.cfg", "firmware.cfg.bak"));
+        assert!(matches("*.lvbitx;*.cfg", "firmware.cfg"));
+        assert!(!matches("", "firmware.cfg"));
+    }
+
+    #[test]
+    fn test_version_in_file_name() {
+        assert_eq!(
+            version_in_file_name("cDAQ-9189_1.2.0f3.cfg"),
+            Some(FirmwareVersion::from("1.2.0f3"))
+        );
+        assert_eq!(
+            version_in_file_name("NI-9189_v20.5.cfg"),
+            Some(FirmwareVersion::from("20.5"))
+        );
+        assert_eq!(version_in_file_name("cDAQ-9189.cfg"), None);
+    }
+
+    #[test]
+    fn test_find_in_directory() {
+        let directory =
+            std::env::temp_dir().join(format!("ni-syscfg-firmware-test-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        for name in [
+            "cDAQ-9189_1.10.0.cfg",
+            "cDAQ-9189_1.2.0.cfg",
+            "cDAQ-9189_1.2.0b1.cfg",
+            "cDAQ-9185_2.0.0.cfg",
+            "readme.txt",
+        ] {
+            fs::write(directory.join(name), b"").unwrap();
+        }
+        fs::create_dir_all(directory.join("cDAQ-9189_9.9.9.cfg")).unwrap();
+
+        let files = FirmwareFilePattern::from("cDAQ-9189_*.cfg")
+            .find_in(&directory)
+            .unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let names: Vec<String> = files
+            .iter()
+            .map(|file| {
+                file.path
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "cDAQ-9189_1.2.0b1.cfg",
+                "cDAQ-9189_1.2.0.cfg",
+                "cDAQ-9189_1.10.0.cfg"
+            ]
+        );
+        assert_eq!(files[2].version, Some(FirmwareVersion::from("1.10.0")));
+    }
+}
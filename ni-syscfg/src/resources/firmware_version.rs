@@ -0,0 +1,225 @@
+//! Firmware versions and update modes reported by hardware resources.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use ni_syscfg_sys::*;
+use num_derive::FromPrimitive;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::parameters::{ReadableParameter, ValueEnum};
+
+/// A firmware version string parsed so that versions can be compared and sorted.
+///
+/// NI versions are normally of the form `major.minor.fix` followed by an optional
+/// release stage and build number, e.g. `20.0.0f1`. The stages sort as
+/// `d` (development) < `a` (alpha) < `b` (beta) < `f` (final), and a version without
+/// a stage is treated as final.
+///
+/// Any string is accepted. Parts which aren't numeric are ignored for ordering but
+/// the original text is kept for display and for passing back to the API.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FirmwareVersion {
+    text: String,
+    numbers: Vec<u32>,
+    stage: u8,
+    build: u32,
+}
+
+const FINAL_STAGE: u8 = 3;
+
+impl From<&str> for FirmwareVersion {
+    fn from(text: &str) -> Self {
+        let text = text.trim();
+        let mut numbers = Vec::new();
+        let mut stage = FINAL_STAGE;
+        let mut build = 0;
+
+        for part in text.split('.') {
+            let digits_end = part
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(part.len());
+            if let Ok(number) = part[..digits_end].parse() {
+                numbers.push(number);
+            }
+            let mut suffix = part[digits_end..].chars();
+            if let Some(stage_char) = suffix.next() {
+                stage = stage_rank(stage_char);
+                build = suffix.as_str().parse().unwrap_or(0);
+            }
+        }
+
+        Self {
+            text: text.to_string(),
+            numbers,
+            stage,
+            build,
+        }
+    }
+}
+
+fn stage_rank(stage: char) -> u8 {
+    match stage.to_ascii_lowercase() {
+        'd' => 0,
+        'a' => 1,
+        'b' => 2,
+        _ => FINAL_STAGE,
+    }
+}
+
+impl FirmwareVersion {
+    /// The version as reported by the API.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The numeric parts of the version, e.g. `[20, 0, 0]` for `20.0.0f1`.
+    pub fn numbers(&self) -> &[u32] {
+        &self.numbers
+    }
+
+    fn number(&self, index: usize) -> u32 {
+        self.numbers.get(index).copied().unwrap_or(0)
+    }
+}
+
+impl Ord for FirmwareVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let length = self.numbers.len().max(other.numbers.len());
+        (0..length)
+            .map(|index| self.number(index).cmp(&other.number(index)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+            .then(self.stage.cmp(&other.stage))
+            .then(self.build.cmp(&other.build))
+            .then_with(|| self.text.cmp(&other.text))
+    }
+}
+
+impl PartialOrd for FirmwareVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// How the firmware on a device is updated.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive)]
+pub enum FirmwareUpdateMode {
+    /// The device has no updatable firmware.
+    None = NISysCfgFirmwareUpdateMode_NISysCfgFirmwareUpdateModeNone,
+    /// The firmware is updated by the user, e.g. with [HardwareResource::upgrade_firmware_from_file].
+    Manual = NISysCfgFirmwareUpdateMode_NISysCfgFirmwareUpdateModeManual,
+    /// The driver keeps the firmware up to date automatically.
+    DriverManaged = NISysCfgFirmwareUpdateMode_NISysCfgFirmwareUpdateModeDriverManaged,
+}
+
+impl ValueEnum for FirmwareUpdateMode {}
+
+/// The current firmware on a device and how it can be updated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FirmwareInfo {
+    /// The current firmware revision, if the device reports one.
+    pub revision: Option<FirmwareVersion>,
+    pub update_mode: FirmwareUpdateMode,
+}
+
+impl HardwareResource {
+    /// The firmware versions which the driver is able to install on the device, sorted from oldest to newest.
+    ///
+    /// Any of these can be passed to [HardwareResource::upgrade_firmware_version] using [FirmwareVersion::as_str].
+    pub fn available_firmware_versions(&self) -> Result<Vec<FirmwareVersion>> {
+        let count = self
+            .optional_parameter::<i32>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfAvailableFirmwareVersions,
+            )?
+            .unwrap_or(0);
+
+        let mut versions = (0..count.max(0) as u32)
+            .map(|index| {
+                String::read_resource_indexed_parameter(
+                    self.handle,
+                    NISysCfgIndexedProperty_NISysCfgIndexedPropertyAvailableFirmwareVersion,
+                    index,
+                )
+                .map(|version| FirmwareVersion::from(version.as_str()))
+            })
+            .collect::<Result<Vec<FirmwareVersion>>>()?;
+        versions.sort();
+        Ok(versions)
+    }
+
+    /// The current firmware revision and the update mode of the device.
+    pub fn firmware_info(&self) -> Result<FirmwareInfo> {
+        let revision = self
+            .optional_parameter::<String>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyFirmwareRevision,
+            )?
+            .filter(|revision| !revision.is_empty())
+            .map(|revision| FirmwareVersion::from(revision.as_str()));
+        let update_mode = self
+            .optional_parameter::<FirmwareUpdateMode>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyFirmwareUpdateMode,
+            )?
+            .unwrap_or(FirmwareUpdateMode::None);
+        Ok(FirmwareInfo {
+            revision,
+            update_mode,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(text: &str) -> FirmwareVersion {
+        FirmwareVersion::from(text)
+    }
+
+    #[test]
+    fn test_parse_version() {
+        let parsed = version("20.0.0f1");
+        assert_eq!(parsed.numbers(), &[20, 0, 0]);
+        assert_eq!(parsed.stage, FINAL_STAGE);
+        assert_eq!(parsed.build, 1);
+        assert_eq!(parsed.to_string(), "20.0.0f1");
+    }
+
+    #[test]
+    fn test_numeric_ordering() {
+        assert!(version("1.10.0") > version("1.9.0"));
+        assert!(version("2.0") > version("1.99.99"));
+        assert!(version("1.0.1") > version("1.0"));
+    }
+
+    #[test]
+    fn test_stage_ordering() {
+        assert!(version("2.0.0d5") < version("2.0.0a1"));
+        assert!(version("2.0.0a1") < version("2.0.0b1"));
+        assert!(version("2.0.0b9") < version("2.0.0f0"));
+        assert!(version("2.0.0f0") < version("2.0.0f1"));
+    }
+
+    #[test]
+    fn test_sort_versions() {
+        let mut versions = [version("1.2.0"), version("1.10.0"), version("1.2.0b1")];
+        versions.sort();
+        let sorted: Vec<&str> = versions.iter().map(|v| v.as_str()).collect();
+        assert_eq!(sorted, ["1.2.0b1", "1.2.0", "1.10.0"]);
+    }
+
+    #[test]
+    fn test_unparsable_version_is_kept() {
+        let parsed = version("unknown");
+        assert!(parsed.numbers().is_empty());
+        assert_eq!(parsed.as_str(), "unknown");
+    }
+}
@@ -0,0 +1,378 @@
+//! Identifying the same physical device across reboots and hardware changes.
+//!
+//! Aliases and handles can change when hardware is moved or reinstalled, so devices are
+//! identified by their vendor, product and serial number, falling back to where they are
+//! on the bus for devices which don't report a serial number.
+
+use std::fmt;
+use std::str::FromStr;
+
+use ni_syscfg_sys::*;
+use thiserror::Error;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::session::Session;
+
+/// A key for a physical device which is stable across reboots.
+///
+/// This can be saved as text using [fmt::Display] and read back with [FromStr].
+/// The text form is `VVVV:PPPP:<serial>@<bus address>` with the ids in hex. The serial number
+/// may contain `@` but the bus address can't.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeviceIdentity {
+    pub vendor_id: u32,
+    pub product_id: u32,
+    pub serial_number: Option<String>,
+    /// Where the device is connected, such as the PCI address or the chassis link and slot.
+    pub bus_address: Option<String>,
+}
+
+impl DeviceIdentity {
+    /// Returns true if both identities refer to the same physical device, even if it has moved.
+    ///
+    /// Devices with serial numbers are compared by serial number. Devices without are
+    /// compared by bus address, so they can't be followed if they move.
+    pub fn same_device(&self, other: &DeviceIdentity) -> bool {
+        if self.vendor_id != other.vendor_id || self.product_id != other.product_id {
+            return false;
+        }
+        match (&self.serial_number, &other.serial_number) {
+            (Some(serial), Some(other_serial)) => serial == other_serial,
+            (None, None) => self.bus_address.is_some() && self.bus_address == other.bus_address,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for DeviceIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04X}:{:04X}:{}@{}",
+            self.vendor_id,
+            self.product_id,
+            self.serial_number.as_deref().unwrap_or(""),
+            self.bus_address.as_deref().unwrap_or("")
+        )
+    }
+}
+
+/// The error returned when text isn't a valid [DeviceIdentity].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid device identity: {0}")]
+pub struct ParseDeviceIdentityError(String);
+
+impl FromStr for DeviceIdentity {
+    type Err = ParseDeviceIdentityError;
+
+    fn from_str(text: &str) -> std::result::Result<Self, Self::Err> {
+        let error = || ParseDeviceIdentityError(text.to_string());
+        let mut parts = text.trim().splitn(3, ':');
+        let vendor_id = parts.next().ok_or_else(error)?;
+        let product_id = parts.next().ok_or_else(error)?;
+        let (serial_number, bus_address) = parts
+            .next()
+            .and_then(|rest| rest.rsplit_once('@'))
+            .ok_or_else(error)?;
+
+        let non_empty = |text: &str| (!text.is_empty()).then(|| text.to_string());
+        let identity = DeviceIdentity {
+            vendor_id: u32::from_str_radix(vendor_id, 16).map_err(|_| error())?,
+            product_id: u32::from_str_radix(product_id, 16).map_err(|_| error())?,
+            serial_number: non_empty(serial_number),
+            bus_address: non_empty(bus_address),
+        };
+        if identity.serial_number.is_none() && identity.bus_address.is_none() {
+            return Err(error());
+        }
+        Ok(identity)
+    }
+}
+
+/// A device currently in the system with its identity.
+///
+/// This holds the name rather than the [HardwareResource] so it can be kept after the session
+/// closes. Use [Session::resource_for] to get the resource back.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdentifiedDevice {
+    pub identity: DeviceIdentity,
+    pub name: String,
+}
+
+/// A device from a saved list which is now at a different bus address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MovedDevice {
+    pub saved: DeviceIdentity,
+    pub current: IdentifiedDevice,
+}
+
+/// The result of comparing a saved list of identities against the devices in the system.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IdentityReport {
+    /// Saved devices which are still at the same bus address.
+    pub present: Vec<IdentifiedDevice>,
+    /// Saved devices which are now at a different bus address.
+    pub moved: Vec<MovedDevice>,
+    /// Saved devices which can't be found.
+    pub missing: Vec<DeviceIdentity>,
+    /// Devices in the system which aren't in the saved list.
+    pub new: Vec<IdentifiedDevice>,
+}
+
+impl IdentityReport {
+    /// Returns true if every saved device is present at the same address and there are no new devices.
+    pub fn unchanged(&self) -> bool {
+        self.moved.is_empty() && self.missing.is_empty() && self.new.is_empty()
+    }
+}
+
+impl HardwareResource {
+    /// The stable identity of the device, or [None] if it has neither a serial number nor a bus address.
+    pub fn identity(&self) -> Result<Option<DeviceIdentity>> {
+        let vendor_id = self
+            .optional_parameter::<u32>(NISysCfgResourceProperty_NISysCfgResourcePropertyVendorId)?
+            .unwrap_or(0);
+        let product_id = self
+            .optional_parameter::<u32>(NISysCfgResourceProperty_NISysCfgResourcePropertyProductId)?
+            .unwrap_or(0);
+        // Some devices report a serial number of 0 when they don't have one.
+        let serial_number = self
+            .optional_parameter::<String>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertySerialNumber,
+            )?
+            .map(|serial| serial.trim().to_string())
+            .filter(|serial| !serial.is_empty() && serial != "0");
+
+        let bus_address = match self.pci_address()? {
+            Some(address) => Some(address.to_string()),
+            None => {
+                let link = self
+                    .optional_parameter::<String>(
+                        NISysCfgResourceProperty_NISysCfgResourcePropertyConnectsToLinkName,
+                    )?
+                    .filter(|link| !link.is_empty());
+                link.zip(self.slot_number()?)
+                    .map(|(link, slot)| format!("{link}/{slot}"))
+            }
+        };
+
+        if serial_number.is_none() && bus_address.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(DeviceIdentity {
+            vendor_id,
+            product_id,
+            serial_number,
+            bus_address,
+        }))
+    }
+}
+
+impl Session {
+    /// The identities of all the devices in the system, for saving and comparing later.
+    pub fn device_identities(&self) -> Result<Vec<IdentifiedDevice>> {
+        let mut devices = Vec::new();
+        for hardware in self.find_hardware(None, None)? {
+            if let Some(identity) = hardware.identity()? {
+                devices.push(IdentifiedDevice {
+                    identity,
+                    name: hardware.name()?,
+                });
+            }
+        }
+        Ok(devices)
+    }
+
+    /// Find the device with the `identity`, even if it has moved to a different bus address.
+    pub fn find_by_identity(&self, identity: &DeviceIdentity) -> Result<Option<HardwareResource>> {
+        for hardware in self.find_hardware(None, None)? {
+            if hardware
+                .identity()?
+                .is_some_and(|current| current.same_device(identity))
+            {
+                return Ok(Some(hardware));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The resource for a device returned by [Session::device_identities] or in an [IdentityReport].
+    ///
+    /// This matches the identity exactly, so it returns [None] if the device has since moved.
+    pub fn resource_for(&self, device: &IdentifiedDevice) -> Result<Option<HardwareResource>> {
+        for hardware in self.find_hardware(None, None)? {
+            if hardware.identity()?.as_ref() == Some(&device.identity) {
+                return Ok(Some(hardware));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Compare a saved list of identities against the devices now in the system.
+    ///
+    /// The report holds the identities and names of the current devices. Pass them to
+    /// [Session::resource_for] to act on them.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::{DeviceIdentity, SessionConfig};
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    /// let saved: Vec<DeviceIdentity> = ["3923:7514:01ABC123@1a:00.0"]
+    ///   .iter()
+    ///   .map(|text| text.parse().unwrap())
+    ///   .collect();
+    ///
+    /// let report = session.compare_identities(&saved).unwrap();
+    /// for missing in report.missing {
+    ///   println!("{missing} is missing");
+    /// }
+    /// for moved in report.moved {
+    ///   if let Some(hardware) = session.resource_for(&moved.current).unwrap() {
+    ///     println!("{} moved to {:?}", moved.saved, hardware.pci_address().unwrap());
+    ///   }
+    /// }
+    /// ```
+    pub fn compare_identities(&self, saved: &[DeviceIdentity]) -> Result<IdentityReport> {
+        Ok(compare_identities(saved, self.device_identities()?))
+    }
+}
+
+fn compare_identities(saved: &[DeviceIdentity], current: Vec<IdentifiedDevice>) -> IdentityReport {
+    let mut current: Vec<Option<IdentifiedDevice>> = current.into_iter().map(Some).collect();
+    let mut report = IdentityReport::default();
+    let mut unmatched = Vec::new();
+
+    // Exact matches are taken first so a moved device can't claim another device's place.
+    for identity in saved {
+        match take_first(&mut current, |device| &device.identity == identity) {
+            Some(device) => report.present.push(device),
+            None => unmatched.push(identity),
+        }
+    }
+
+    for identity in unmatched {
+        match take_first(&mut current, |device| device.identity.same_device(identity)) {
+            Some(device) => report.moved.push(MovedDevice {
+                saved: identity.clone(),
+                current: device,
+            }),
+            None => report.missing.push(identity.clone()),
+        }
+    }
+
+    report.new = current.into_iter().flatten().collect();
+    report
+}
+
+fn take_first(
+    devices: &mut [Option<IdentifiedDevice>],
+    matches: impl Fn(&IdentifiedDevice) -> bool,
+) -> Option<IdentifiedDevice> {
+    devices
+        .iter_mut()
+        .find(|device| device.as_ref().is_some_and(&matches))?
+        .take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(serial: Option<&str>, address: &str) -> DeviceIdentity {
+        DeviceIdentity {
+            vendor_id: 0x1093,
+            product_id: 0x7514,
+            serial_number: serial.map(str::to_string),
+            bus_address: Some(address.to_string()),
+        }
+    }
+
+    fn device(identity: DeviceIdentity, name: &str) -> IdentifiedDevice {
+        IdentifiedDevice {
+            identity,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_identity_text_round_trip() {
+        let with_serial = identity(Some("01ABC123"), "1a:00.0");
+        assert_eq!(with_serial.to_string(), "1093:7514:01ABC123@1a:00.0");
+        assert_eq!(with_serial.to_string().parse(), Ok(with_serial));
+
+        let without_serial = identity(None, "PXI1/3");
+        assert_eq!(without_serial.to_string(), "1093:7514:@PXI1/3");
+        assert_eq!(without_serial.to_string().parse(), Ok(without_serial));
+    }
+
+    #[test]
+    fn test_serial_with_at_sign_round_trip() {
+        let with_at = identity(Some("SN@42"), "PXI1/3");
+        assert_eq!(with_at.to_string(), "1093:7514:SN@42@PXI1/3");
+        assert_eq!(with_at.to_string().parse(), Ok(with_at));
+
+        let no_address = DeviceIdentity {
+            bus_address: None,
+            ..identity(Some("SN@42"), "")
+        };
+        assert_eq!(no_address.to_string().parse(), Ok(no_address));
+    }
+
+    #[test]
+    fn test_invalid_identity_text() {
+        assert!("1093:7514".parse::<DeviceIdentity>().is_err());
+        assert!("1093:7514:@".parse::<DeviceIdentity>().is_err());
+        assert!("XYZ:7514:01ABC123@".parse::<DeviceIdentity>().is_err());
+    }
+
+    #[test]
+    fn test_same_device() {
+        let original = identity(Some("01ABC123"), "1a:00.0");
+        assert!(original.same_device(&identity(Some("01ABC123"), "1b:00.0")));
+        assert!(!original.same_device(&identity(Some("01ABC124"), "1a:00.0")));
+        assert!(!original.same_device(&identity(None, "1a:00.0")));
+        assert!(identity(None, "PXI1/3").same_device(&identity(None, "PXI1/3")));
+        assert!(!identity(None, "PXI1/3").same_device(&identity(None, "PXI1/4")));
+    }
+
+    #[test]
+    fn test_compare_identities() {
+        let saved = [
+            identity(Some("A"), "1a:00.0"),
+            identity(Some("B"), "1b:00.0"),
+            identity(Some("C"), "1c:00.0"),
+            identity(None, "PXI1/5"),
+        ];
+        let current = vec![
+            device(identity(Some("A"), "1a:00.0"), "Dev1"),
+            device(identity(Some("B"), "1d:00.0"), "Dev2"),
+            device(identity(None, "PXI1/5"), "Dev3"),
+            device(identity(Some("D"), "1c:00.0"), "Dev4"),
+        ];
+
+        let report = compare_identities(&saved, current);
+
+        let names = |devices: &[IdentifiedDevice]| {
+            devices
+                .iter()
+                .map(|device| device.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&report.present), ["Dev1", "Dev3"]);
+        assert_eq!(report.moved.len(), 1);
+        assert_eq!(report.moved[0].saved, saved[1]);
+        assert_eq!(report.moved[0].current.name, "Dev2");
+        assert_eq!(report.missing, [saved[2].clone()]);
+        assert_eq!(names(&report.new), ["Dev4"]);
+        assert!(!report.unchanged());
+    }
+
+    #[test]
+    fn test_unchanged_system() {
+        let saved = [identity(Some("A"), "1a:00.0")];
+        let current = vec![device(saved[0].clone(), "Dev1")];
+        assert!(compare_identities(&saved, current).unchanged());
+    }
+}
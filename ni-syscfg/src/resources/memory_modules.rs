@@ -0,0 +1,122 @@
+//! Memory module (DIMM) inventory of PXI and real-time controllers.
+
+use ni_syscfg_sys::*;
+
+use super::HardwareResource;
+use crate::error::{NiSysCfgApiStatus, NiSystemConfigurationError, Result};
+use crate::parameters::ReadableParameter;
+use crate::types::BYTES_PER_MEGABYTE;
+
+/// An upper limit on the number of modules to read in case the end of the list isn't reported.
+const MAX_MEMORY_MODULES: u32 = 64;
+
+/// A memory module installed in a controller.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryModule {
+    /// The socket the module is installed in.
+    pub location: String,
+    pub model: String,
+    pub capacity_bytes: u64,
+}
+
+impl HardwareResource {
+    /// The memory modules installed in the controller.
+    ///
+    /// This is empty for resources which aren't controllers or which don't report their memory.
+    pub fn memory_modules(&self) -> Result<Vec<MemoryModule>> {
+        read_until_end(|index| self.memory_module(index))
+    }
+
+    fn memory_module(&self, index: u32) -> Result<MemoryModule> {
+        let location = String::read_resource_indexed_parameter(
+            self.handle,
+            NISysCfgControllerProperty_NISysCfgControllerPropertyMemoryModuleLocation,
+            index,
+        )?;
+        let model = String::read_resource_indexed_parameter(
+            self.handle,
+            NISysCfgControllerProperty_NISysCfgControllerPropertyMemoryModuleModelName,
+            index,
+        )?;
+        let capacity_megabytes = u32::read_resource_indexed_parameter(
+            self.handle,
+            NISysCfgControllerProperty_NISysCfgControllerPropertyMemoryModuleCapacity,
+            index,
+        )?;
+        Ok(MemoryModule {
+            location,
+            model,
+            capacity_bytes: capacity_megabytes as u64 * BYTES_PER_MEGABYTE,
+        })
+    }
+}
+
+/// Read indexed items until one can't be read, as there is no count of memory modules.
+///
+/// The list ends at the first index which doesn't exist. Any other error, such as a lost
+/// connection, is returned rather than cutting the inventory short.
+fn read_until_end<T>(mut read: impl FnMut(u32) -> Result<T>) -> Result<Vec<T>> {
+    let mut items = Vec::new();
+    for index in 0..MAX_MEMORY_MODULES {
+        match read(index) {
+            Ok(item) => items.push(item),
+            Err(NiSystemConfigurationError::ApiError(
+                NiSysCfgApiStatus::PropDoesNotExist | NiSysCfgApiStatus::ItemDoesNotExist,
+            )) => break,
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_modules(count: u32, end: NiSysCfgApiStatus) -> Result<Vec<u32>> {
+        let mut end = Some(end);
+        read_until_end(|index| {
+            if index < count {
+                Ok(index)
+            } else {
+                Err(NiSystemConfigurationError::ApiError(end.take().unwrap()))
+            }
+        })
+    }
+
+    #[test]
+    fn test_missing_index_ends_the_list() {
+        assert_eq!(
+            read_modules(2, NiSysCfgApiStatus::ItemDoesNotExist).unwrap(),
+            [0, 1]
+        );
+        assert_eq!(
+            read_modules(2, NiSysCfgApiStatus::PropDoesNotExist).unwrap(),
+            [0, 1]
+        );
+    }
+
+    #[test]
+    fn test_other_errors_are_returned() {
+        assert!(read_modules(2, NiSysCfgApiStatus::Timeout).is_err());
+        assert!(read_modules(2, NiSysCfgApiStatus::Unknown(-1)).is_err());
+    }
+
+    #[test]
+    fn test_first_module() {
+        assert!(read_modules(0, NiSysCfgApiStatus::PropDoesNotExist)
+            .unwrap()
+            .is_empty());
+        assert!(read_modules(0, NiSysCfgApiStatus::Unknown(-1)).is_err());
+    }
+
+    #[test]
+    fn test_read_is_bounded() {
+        assert_eq!(
+            read_modules(u32::MAX, NiSysCfgApiStatus::Unknown(-1))
+                .unwrap()
+                .len(),
+            MAX_MEMORY_MODULES as usize
+        );
+    }
+}
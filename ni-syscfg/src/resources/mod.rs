@@ -0,0 +1,219 @@
+mod alarms;
+mod calibration;
+mod cpu;
+mod expert_info;
+mod filter_match;
+mod firmware;
+mod firmware_file;
+mod firmware_version;
+mod identity;
+mod memory_modules;
+mod pci;
+mod power_supply;
+mod program_mode;
+mod sensors;
+mod services;
+mod tcp;
+mod tree;
+mod visa;
+
+use std::collections::VecDeque;
+use std::ffi::c_char;
+use std::marker::PhantomData;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{api_status, NiSysCfgApiStatus, Result};
+use crate::handles::{close_handle, free_detailed_string};
+use crate::parameters::{optional_property, ApiBool, BusType, ReadableParameter};
+use crate::session::Session;
+use ni_syscfg_sys::*;
+
+pub use alarms::{Alarm, AlarmEvaluator, AlarmKind, Severity};
+pub use calibration::CalibrationInfo;
+pub use cpu::{CpuInfo, CpuLoad};
+pub use expert_info::ExpertInfo;
+pub use firmware::{FirmwareProgress, FirmwareStatus, FirmwareUpdate, FirmwareUpdateOptions};
+pub use firmware_file::{FirmwareFile, FirmwareFilePattern};
+pub use firmware_version::{FirmwareInfo, FirmwareUpdateMode, FirmwareVersion};
+pub use identity::{
+    DeviceIdentity, IdentifiedDevice, IdentityReport, MovedDevice, ParseDeviceIdentityError,
+};
+pub use memory_modules::MemoryModule;
+pub use pci::{PciAddress, PciLink};
+pub use power_supply::{PowerSupply, PowerSupplyState};
+pub use program_mode::ModuleProgramMode;
+pub use sensors::{SensorKind, SensorReading, SensorReadings};
+pub use services::ServiceType;
+pub use tcp::{LanInstrument, MacAddress, ParseMacAddressError, TcpInstrumentInfo};
+pub use tree::{HardwareNode, HardwareTree, HardwareTreeEntry};
+
+/// The hardware resource list is an iterator provided by [crate::Session::find_hardware].
+///
+/// Iterate through it to access [HardwareResource].
+pub struct HardwareResourceList<'a> {
+    handle: NISysCfgEnumResourceHandle,
+    session_handle: NISysCfgSessionHandle,
+    /// Resources already read from the handle, which are returned first.
+    buffered: VecDeque<HardwareResource>,
+    session: PhantomData<&'a Session>,
+}
+
+impl<'a> Drop for HardwareResourceList<'a> {
+    fn drop(&mut self) {
+        //ignore result in drop.
+        let _ = close_handle(self.handle);
+    }
+}
+
+impl<'a> HardwareResourceList<'a> {
+    pub fn from_handle(handle: NISysCfgEnumResourceHandle, session: &'a Session) -> Self {
+        Self::from_session_handle(handle, session.handle())
+    }
+
+    /// Create the list from the raw session handle for session types which don't hold a [Session].
+    ///
+    /// The caller must make sure the list doesn't outlive the session.
+    pub(crate) fn from_session_handle(
+        handle: NISysCfgEnumResourceHandle,
+        session_handle: NISysCfgSessionHandle,
+    ) -> Self {
+        Self {
+            handle,
+            session_handle,
+            buffered: VecDeque::new(),
+            session: PhantomData,
+        }
+    }
+
+    /// Read every remaining resource now, returning them from the list in the same order.
+    ///
+    /// Each resource is passed to `check` as it is read and any error is returned immediately.
+    pub(crate) fn read_ahead(
+        mut self,
+        mut check: impl FnMut(&HardwareResource) -> Result<()>,
+    ) -> Result<Self> {
+        let mut buffered = VecDeque::new();
+        for hardware in self.by_ref() {
+            check(&hardware)?;
+            buffered.push_back(hardware);
+        }
+        self.buffered = buffered;
+        Ok(self)
+    }
+}
+
+impl<'a> Iterator for HardwareResourceList<'a> {
+    type Item = HardwareResource;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(hardware) = self.buffered.pop_front() {
+            return Some(hardware);
+        }
+        unsafe {
+            let mut resource_handle = std::ptr::null_mut();
+            let result = api_status(NISysCfgNextResource(
+                self.session_handle,
+                self.handle,
+                &mut resource_handle,
+            ));
+
+            match result {
+                Ok(NiSysCfgApiStatus::EndOfEnum) => None,
+                Ok(_) => Some(HardwareResource::from_handle(resource_handle)),
+                Err(_) => None,
+            }
+        }
+    }
+}
+
+pub struct HardwareResource {
+    handle: NISysCfgResourceHandle,
+}
+
+pub struct ResourceParameter<T: ReadableParameter> {
+    id: i32,
+    phantom: PhantomData<T>,
+}
+impl HardwareResource {
+    pub fn from_handle(handle: NISysCfgResourceHandle) -> Self {
+        Self { handle }
+    }
+
+    pub fn name(&self) -> Result<String> {
+        String::read_resource_indexed_parameter(
+            self.handle,
+            NISysCfgIndexedProperty_NISysCfgIndexedPropertyExpertUserAlias,
+            0,
+        )
+    }
+
+    pub fn get_parameter<T: ReadableParameter>(
+        &self,
+        parameter: ResourceParameter<T>,
+    ) -> Result<T> {
+        T::read_resource_parameter(self.handle, parameter.id)
+    }
+
+    /// Read a parameter where a property that doesn't exist on this resource is [None].
+    fn optional_parameter<T: ReadableParameter>(
+        &self,
+        id: NISysCfgResourceProperty,
+    ) -> Result<Option<T>> {
+        optional_property(T::read_resource_parameter(self.handle, id))
+    }
+
+    /// Read a boolean where a missing property is treated as false.
+    fn optional_bool(&self, id: NISysCfgResourceProperty) -> Result<bool> {
+        Ok(self
+            .optional_parameter::<ApiBool>(id)?
+            .is_some_and(|value| value.into()))
+    }
+
+    /// Read a timestamp where a missing property or an unset (zero) time is [None].
+    fn optional_time(&self, id: NISysCfgResourceProperty) -> Result<Option<SystemTime>> {
+        Ok(self
+            .optional_parameter::<SystemTime>(id)?
+            .filter(|&time| time > UNIX_EPOCH))
+    }
+
+    /// Save any property changes made on the resource handle.
+    ///
+    /// Returns true if the changes require the system to be restarted.
+    pub(crate) fn save_changes(&self) -> Result<bool> {
+        let mut restart_required = ApiBool::False as NISysCfgBool;
+        let mut detailed_result: *mut c_char = std::ptr::null_mut();
+        let status = unsafe {
+            NISysCfgSaveResourceChanges(self.handle, &mut restart_required, &mut detailed_result)
+        };
+        free_detailed_string(detailed_result);
+        api_status(status)?;
+        Ok(restart_required != ApiBool::False as NISysCfgBool)
+    }
+
+    //Specific parameters follow.
+    pub fn connects_to_bus_type(&self) -> Result<BusType> {
+        BusType::read_resource_parameter(
+            self.handle,
+            NISysCfgResourceProperty_NISysCfgResourcePropertyConnectsToBusType,
+        )
+    }
+
+    /// Returns true if this is a simulated device rather than physical hardware.
+    pub fn is_simulated(&self) -> Result<bool> {
+        self.optional_bool(NISysCfgResourceProperty_NISysCfgResourcePropertyIsSimulated)
+    }
+
+    /// The slot the resource is in, or [None] if it isn't in a slot.
+    pub fn slot_number(&self) -> Result<Option<i32>> {
+        Ok(self
+            .optional_parameter::<i32>(NISysCfgResourceProperty_NISysCfgResourcePropertySlotNumber)?
+            .filter(|&slot| slot > 0))
+    }
+}
+
+impl Drop for HardwareResource {
+    fn drop(&mut self) {
+        //ignore result in drop.
+        let _ = close_handle(self.handle);
+    }
+}
@@ -0,0 +1,159 @@
+//! PCI and PCI Express location and link information for PXI and PCI devices.
+
+use std::fmt;
+
+use ni_syscfg_sys::*;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::session::Session;
+
+/// The location of a device on the PCI bus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PciAddress {
+    pub bus: u32,
+    pub device: u32,
+    pub function: u32,
+}
+
+impl fmt::Display for PciAddress {
+    /// Formats the address as `bus:device.function` in hex, matching tools like `lspci`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02x}:{:02x}.{:x}",
+            self.bus, self.device, self.function
+        )
+    }
+}
+
+/// The PCI Express link of a device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PciLink {
+    pub address: PciAddress,
+    /// The number of lanes the link trained at.
+    pub width: i32,
+    /// The number of lanes the device supports.
+    pub max_width: i32,
+}
+
+impl PciLink {
+    /// Returns true if the link is running with fewer lanes than the device supports.
+    pub fn is_degraded(&self) -> bool {
+        self.width > 0 && self.max_width > 0 && self.width < self.max_width
+    }
+}
+
+impl HardwareResource {
+    /// The PCI address of the device, or [None] if it isn't on a PCI bus.
+    pub fn pci_address(&self) -> Result<Option<PciAddress>> {
+        let bus = self.optional_parameter::<u32>(
+            NISysCfgResourceProperty_NISysCfgResourcePropertyPxiPciBusNumber,
+        )?;
+        let device = self.optional_parameter::<u32>(
+            NISysCfgResourceProperty_NISysCfgResourcePropertyPxiPciDeviceNumber,
+        )?;
+        let function = self.optional_parameter::<u32>(
+            NISysCfgResourceProperty_NISysCfgResourcePropertyPxiPciFunctionNumber,
+        )?;
+
+        Ok(match (bus, device, function) {
+            (Some(bus), Some(device), Some(function)) => Some(PciAddress {
+                bus,
+                device,
+                function,
+            }),
+            _ => None,
+        })
+    }
+
+    /// The PCI Express link of the device, or [None] if it doesn't report a link width.
+    pub fn pci_link(&self) -> Result<Option<PciLink>> {
+        let Some(address) = self.pci_address()? else {
+            return Ok(None);
+        };
+        let width = self.optional_parameter::<i32>(
+            NISysCfgResourceProperty_NISysCfgResourcePropertyPxiPciLinkWidth,
+        )?;
+        let max_width = self.optional_parameter::<i32>(
+            NISysCfgResourceProperty_NISysCfgResourcePropertyPxiPciMaxLinkWidth,
+        )?;
+
+        Ok(width.zip(max_width).map(|(width, max_width)| PciLink {
+            address,
+            width,
+            max_width,
+        }))
+    }
+}
+
+impl Session {
+    /// Find every device whose PCI Express link is running narrower than its maximum width.
+    ///
+    /// A device which trains at x1 instead of x4 still works but with reduced throughput,
+    /// so this is worth checking after changing cabling or chassis.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::SessionConfig;
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    ///
+    /// for (hardware, link) in session.degraded_pcie_links().unwrap() {
+    ///   println!(
+    ///     "{} at {} is running at x{} of x{}",
+    ///     hardware.name().unwrap(),
+    ///     link.address,
+    ///     link.width,
+    ///     link.max_width
+    ///   );
+    /// }
+    /// ```
+    pub fn degraded_pcie_links(&self) -> Result<Vec<(HardwareResource, PciLink)>> {
+        let mut degraded = Vec::new();
+        for hardware in self.find_hardware(None, None)? {
+            if let Some(link) = hardware.pci_link()? {
+                if link.is_degraded() {
+                    degraded.push((hardware, link));
+                }
+            }
+        }
+        Ok(degraded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(width: i32, max_width: i32) -> PciLink {
+        PciLink {
+            address: PciAddress {
+                bus: 0x1a,
+                device: 0,
+                function: 1,
+            },
+            width,
+            max_width,
+        }
+    }
+
+    #[test]
+    fn test_address_display() {
+        assert_eq!(link(4, 4).address.to_string(), "1a:00.1");
+    }
+
+    #[test]
+    fn test_degraded_link() {
+        assert!(link(1, 4).is_degraded());
+        assert!(!link(4, 4).is_degraded());
+        assert!(!link(8, 4).is_degraded());
+    }
+
+    #[test]
+    fn test_unknown_width_is_not_degraded() {
+        assert!(!link(0, 4).is_degraded());
+        assert!(!link(-1, 4).is_degraded());
+        assert!(!link(1, 0).is_degraded());
+    }
+}
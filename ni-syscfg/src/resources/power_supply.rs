@@ -0,0 +1,180 @@
+//! Power supplies of PXI chassis.
+
+use ni_syscfg_sys::*;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::hardware_filter::HardwareFilter;
+use crate::parameters::{ReadableParameter, ValueEnum};
+use crate::session::Session;
+
+/// The state of a PXI chassis power supply.
+///
+/// `Fault*` states have turned the supply off. `Alert*` states are warnings while the supply
+/// is still running.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
+pub enum PowerSupplyState {
+    Off = NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateOff,
+    On = NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateOn,
+    Faulted = NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFaulted,
+    FaultOutputVoltageOvervoltage12V =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_OutputVoltageOvervoltage12V,
+    FaultOutputVoltageUndervoltage12V =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_OutputVoltageUndervoltage12V,
+    FaultOutputVoltageOvervoltage5VAux =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_OutputVoltageOvervoltage5VAux,
+    FaultOutputVoltageUndervoltage5VAux =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_OutputVoltageUndervoltage5VAux,
+    FaultOutputCurrentOvercurrent12V =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_OutputCurrentOvercurrent12V,
+    FaultOutputCurrentOvercurrent5VAux =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_OutputCurrentOvercurrent5VAux,
+    FaultInputVoltageOvervoltage =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_InputVoltageOvervoltage,
+    FaultInputVoltageUndervoltage =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_InputVoltageUndervoltage,
+    FaultLowerAmbientTemperature =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_LowerAmbientTemperature,
+    FaultUpperAmbientTemperature =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_UpperAmbientTemperature,
+    FaultLowerInternalTemperature =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_LowerInternalTemperature,
+    FaultUpperInternalTemperature =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_UpperInternalTemperature,
+    FaultFan = NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateFault_Fan,
+    AlertOutputVoltageOvervoltage12V =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_OutputVoltageOvervoltage12V,
+    AlertOutputVoltageUndervoltage12V =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_OutputVoltageUndervoltage12V,
+    AlertOutputVoltageOvervoltage5VAux =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_OutputVoltageOvervoltage5VAux,
+    AlertOutputVoltageUndervoltage5VAux =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_OutputVoltageUndervoltage5VAux,
+    AlertOutputCurrentOvercurrent12V =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_OutputCurrentOvercurrent12V,
+    AlertOutputCurrentOvercurrent5VAux =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_OutputCurrentOvercurrent5VAux,
+    AlertOutputCurrentSharing =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_OutputCurrentSharing,
+    AlertInputVoltageOvervoltage =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_InputVoltageOvervoltage,
+    AlertInputVoltageUndervoltage =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_InputVoltageUndervoltage,
+    AlertLowerAmbientTemperature =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_LowerAmbientTemperature,
+    AlertUpperAmbientTemperature =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_UpperAmbientTemperature,
+    AlertLowerInternalTemperature =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_LowerInternalTemperature,
+    AlertUpperInternalTemperature =
+        NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_UpperInternalTemperature,
+    AlertFan = NISysCfgPxiPowerSupplyState_NISysCfgPxiPowerSupplyStateAlert_Fan,
+}
+
+impl ValueEnum for PowerSupplyState {}
+
+impl PowerSupplyState {
+    /// Returns true if the supply is on with no alerts.
+    pub fn is_ok(&self) -> bool {
+        *self == Self::On
+    }
+}
+
+/// A power supply in a PXI chassis.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PowerSupply {
+    pub name: String,
+    /// The state of the supply, or [None] if the API reported a state this crate doesn't know.
+    pub state: Option<PowerSupplyState>,
+    /// The state code as reported by the API, including unknown states.
+    pub state_code: i32,
+}
+
+impl PowerSupply {
+    pub(crate) fn from_state_code(name: String, state_code: i32) -> Self {
+        Self {
+            name,
+            state: PowerSupplyState::from_i32(state_code),
+            state_code,
+        }
+    }
+
+    /// Returns true if the supply is on with no alerts.
+    pub fn is_ok(&self) -> bool {
+        self.state.is_some_and(|state| state.is_ok())
+    }
+}
+
+impl HardwareResource {
+    /// The power supplies in the chassis. This is empty for other resources.
+    pub fn power_supplies(&self) -> Result<Vec<PowerSupply>> {
+        let count = self
+            .optional_parameter::<i32>(NISysCfgPxiProperty_NISysCfgPxiPropertyPowerSupplyBayCount)?
+            .unwrap_or(0);
+
+        (0..count.max(0) as u32)
+            .map(|index| {
+                // Read as a code so a state added in a newer driver doesn't fail the whole list.
+                Ok(PowerSupply::from_state_code(
+                    String::read_resource_indexed_parameter(
+                        self.handle,
+                        NISysCfgPxiIndexedProperty_NISysCfgPxiIndexedPropertyPowerSupplyName,
+                        index,
+                    )?,
+                    i32::read_resource_indexed_parameter(
+                        self.handle,
+                        NISysCfgPxiIndexedProperty_NISysCfgPxiIndexedPropertyPowerSupplyState,
+                        index,
+                    )?,
+                ))
+            })
+            .collect()
+    }
+}
+
+impl Session {
+    /// The power supplies of every chassis with any, paired with the chassis name.
+    pub fn power_supplies(&self) -> Result<Vec<(String, Vec<PowerSupply>)>> {
+        let mut chassis = Vec::new();
+        for hardware in self.find_hardware(Some(&HardwareFilter::chassis_only(self)?), None)? {
+            let supplies = hardware.power_supplies()?;
+            if !supplies.is_empty() {
+                chassis.push((hardware.name()?, supplies));
+            }
+        }
+        Ok(chassis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_from_api() {
+        assert_eq!(PowerSupplyState::from_i32(1), Some(PowerSupplyState::On));
+        assert_eq!(
+            PowerSupplyState::from_i32(28),
+            Some(PowerSupplyState::FaultFan)
+        );
+        assert_eq!(
+            PowerSupplyState::from_i32(54),
+            Some(PowerSupplyState::AlertOutputCurrentSharing)
+        );
+        assert_eq!(PowerSupplyState::from_i32(3), None);
+        assert!(PowerSupplyState::On.is_ok());
+        assert!(!PowerSupplyState::AlertFan.is_ok());
+    }
+
+    #[test]
+    fn test_unknown_state_keeps_code() {
+        let supply = PowerSupply::from_state_code("PS1".to_string(), 3);
+        assert_eq!(supply.state, None);
+        assert_eq!(supply.state_code, 3);
+        assert!(!supply.is_ok());
+        assert!(PowerSupply::from_state_code("PS2".to_string(), 1).is_ok());
+    }
+}
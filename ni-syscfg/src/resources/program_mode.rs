@@ -0,0 +1,62 @@
+//! Program mode of CompactRIO modules.
+
+use ni_syscfg_sys::*;
+use num_derive::FromPrimitive;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::parameters::{ValueEnum, WriteableParameter};
+
+/// How a CompactRIO module is programmed.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, FromPrimitive)]
+pub enum ModuleProgramMode {
+    None = NISysCfgModuleProgramMode_NISysCfgModuleProgramModeNone,
+    /// The module is accessed directly from the real-time processor.
+    RealtimeCpu = NISysCfgModuleProgramMode_NISysCfgModuleProgramModeRealtimeCpu,
+    /// The module is accessed through the scan engine (Scan Mode).
+    RealtimeScan = NISysCfgModuleProgramMode_NISysCfgModuleProgramModeRealtimeScan,
+    /// The module is accessed from LabVIEW FPGA code.
+    LabViewFpga = NISysCfgModuleProgramMode_NISysCfgModuleProgramModeLabVIEWFpga,
+}
+
+impl ValueEnum for ModuleProgramMode {}
+
+impl HardwareResource {
+    /// The program mode of the module, or [None] if this isn't a module which has one.
+    pub fn module_program_mode(&self) -> Result<Option<ModuleProgramMode>> {
+        self.optional_parameter::<ModuleProgramMode>(
+            NISysCfgResourceProperty_NISysCfgResourcePropertyModuleProgramMode,
+        )
+    }
+
+    /// Change the program mode of the module and save the change.
+    ///
+    /// Returns true if the change requires the system to be restarted.
+    pub fn set_module_program_mode(&self, mode: ModuleProgramMode) -> Result<bool> {
+        (mode as i32).write_resource_parameter(
+            self.handle,
+            NISysCfgResourceProperty_NISysCfgResourcePropertyModuleProgramMode,
+        )?;
+        self.save_changes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::FromPrimitive;
+
+    #[test]
+    fn test_mode_from_api() {
+        assert_eq!(
+            ModuleProgramMode::from_i32(2),
+            Some(ModuleProgramMode::RealtimeScan)
+        );
+        assert_eq!(
+            ModuleProgramMode::from_i32(4),
+            Some(ModuleProgramMode::LabViewFpga)
+        );
+        assert_eq!(ModuleProgramMode::from_i32(3), None);
+    }
+}
@@ -0,0 +1,280 @@
+//! Environmental sensors such as temperatures, voltages and fans on controllers and chassis.
+
+use std::fmt;
+
+use ni_syscfg_sys::*;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::experts::ExpertType;
+use crate::hardware_filter::FilterProperty;
+use crate::parameters::{optional_property, ReadableParameter};
+use crate::query::HardwareQuery;
+use crate::session::Session;
+
+/// What a sensor measures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SensorKind {
+    /// Temperature in degrees Celsius.
+    Temperature,
+    /// Voltage in volts.
+    Voltage,
+    /// Fan speed in RPM.
+    Fan,
+    /// Power in watts.
+    Power,
+}
+
+impl SensorKind {
+    /// The unit the readings are in.
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Self::Temperature => "°C",
+            Self::Voltage => "V",
+            Self::Fan => "RPM",
+            Self::Power => "W",
+        }
+    }
+
+    fn properties(&self) -> SensorProperties {
+        match self {
+            Self::Temperature => SensorProperties {
+                count: NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfTemperatureSensors,
+                name: NISysCfgIndexedProperty_NISysCfgIndexedPropertyTemperatureName,
+                reading: NISysCfgIndexedProperty_NISysCfgIndexedPropertyTemperatureReading,
+                nominal: None,
+                lower_critical: Some(
+                    NISysCfgIndexedProperty_NISysCfgIndexedPropertyTemperatureLowerCritical,
+                ),
+                upper_critical: Some(
+                    NISysCfgIndexedProperty_NISysCfgIndexedPropertyTemperatureUpperCritical,
+                ),
+            },
+            Self::Voltage => SensorProperties {
+                count: NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfVoltageSensors,
+                name: NISysCfgIndexedProperty_NISysCfgIndexedPropertyVoltageName,
+                reading: NISysCfgIndexedProperty_NISysCfgIndexedPropertyVoltageReading,
+                nominal: Some(NISysCfgIndexedProperty_NISysCfgIndexedPropertyVoltageNominal),
+                lower_critical: Some(
+                    NISysCfgIndexedProperty_NISysCfgIndexedPropertyVoltageLowerCritical,
+                ),
+                upper_critical: Some(
+                    NISysCfgIndexedProperty_NISysCfgIndexedPropertyVoltageUpperCritical,
+                ),
+            },
+            Self::Fan => SensorProperties {
+                count: NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfFans,
+                name: NISysCfgIndexedProperty_NISysCfgIndexedPropertyFanName,
+                reading: NISysCfgIndexedProperty_NISysCfgIndexedPropertyFanReading,
+                nominal: None,
+                lower_critical: None,
+                upper_critical: None,
+            },
+            Self::Power => SensorProperties {
+                count: NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfPowerSensors,
+                name: NISysCfgIndexedProperty_NISysCfgIndexedPropertyPowerName,
+                reading: NISysCfgIndexedProperty_NISysCfgIndexedPropertyPowerReading,
+                nominal: None,
+                lower_critical: None,
+                upper_critical: Some(
+                    NISysCfgIndexedProperty_NISysCfgIndexedPropertyPowerUpperCritical,
+                ),
+            },
+        }
+    }
+}
+
+/// The API properties for one kind of sensor. Not every kind has every limit.
+struct SensorProperties {
+    count: NISysCfgResourceProperty,
+    name: NISysCfgIndexedProperty,
+    reading: NISysCfgIndexedProperty,
+    nominal: Option<NISysCfgIndexedProperty>,
+    lower_critical: Option<NISysCfgIndexedProperty>,
+    upper_critical: Option<NISysCfgIndexedProperty>,
+}
+
+/// A single reading from a sensor with the limits reported for it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SensorReading {
+    pub kind: SensorKind,
+    pub name: String,
+    pub value: f64,
+    /// The expected value, only reported for voltages.
+    pub nominal: Option<f64>,
+    pub lower_critical: Option<f64>,
+    pub upper_critical: Option<f64>,
+}
+
+impl fmt::Display for SensorReading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} {}", self.name, self.value, self.kind.unit())
+    }
+}
+
+/// All the sensor readings from a resource, grouped by kind.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SensorReadings {
+    pub temperature: Vec<SensorReading>,
+    pub voltage: Vec<SensorReading>,
+    pub fan: Vec<SensorReading>,
+    pub power: Vec<SensorReading>,
+    /// The single temperature reported by devices which don't have indexed temperature sensors.
+    pub current_temp: Option<f64>,
+}
+
+impl SensorReadings {
+    /// The readings of one kind.
+    pub fn of_kind(&self, kind: SensorKind) -> &[SensorReading] {
+        match kind {
+            SensorKind::Temperature => &self.temperature,
+            SensorKind::Voltage => &self.voltage,
+            SensorKind::Fan => &self.fan,
+            SensorKind::Power => &self.power,
+        }
+    }
+
+    /// All of the indexed readings, ordered by kind.
+    pub fn iter(&self) -> impl Iterator<Item = &SensorReading> {
+        self.temperature
+            .iter()
+            .chain(&self.voltage)
+            .chain(&self.fan)
+            .chain(&self.power)
+    }
+
+    /// Returns true if the resource didn't report any sensors.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none() && self.current_temp.is_none()
+    }
+}
+
+impl HardwareResource {
+    /// Read all of the environmental sensors on the resource.
+    ///
+    /// Resources without sensors return empty readings.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::SessionConfig;
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    ///
+    /// for hardware in session.find_hardware(None, None).unwrap() {
+    ///   for reading in hardware.sensors().unwrap().iter() {
+    ///     println!("{}: {reading}", hardware.name().unwrap());
+    ///   }
+    /// }
+    /// ```
+    pub fn sensors(&self) -> Result<SensorReadings> {
+        Ok(SensorReadings {
+            temperature: self.sensors_of_kind(SensorKind::Temperature)?,
+            voltage: self.sensors_of_kind(SensorKind::Voltage)?,
+            fan: self.sensors_of_kind(SensorKind::Fan)?,
+            power: self.sensors_of_kind(SensorKind::Power)?,
+            current_temp: self.current_temperature()?,
+        })
+    }
+
+    /// The current temperature in degrees Celsius of devices which report a single temperature.
+    pub fn current_temperature(&self) -> Result<Option<f64>> {
+        self.optional_parameter::<f64>(NISysCfgResourceProperty_NISysCfgResourcePropertyCurrentTemp)
+    }
+
+    /// Read the sensors of one kind.
+    pub fn sensors_of_kind(&self, kind: SensorKind) -> Result<Vec<SensorReading>> {
+        let properties = kind.properties();
+        let count = self
+            .optional_parameter::<i32>(properties.count)?
+            .unwrap_or(0);
+
+        let mut readings = Vec::new();
+        for index in 0..count.max(0) as u32 {
+            let value = match kind {
+                // Fan speeds are reported as whole RPM.
+                SensorKind::Fan => {
+                    i32::read_resource_indexed_parameter(self.handle, properties.reading, index)?
+                        as f64
+                }
+                _ => f64::read_resource_indexed_parameter(self.handle, properties.reading, index)?,
+            };
+            readings.push(SensorReading {
+                kind,
+                name: String::read_resource_indexed_parameter(self.handle, properties.name, index)?,
+                value,
+                nominal: self.optional_indexed_f64(properties.nominal, index)?,
+                lower_critical: self.optional_indexed_f64(properties.lower_critical, index)?,
+                upper_critical: self.optional_indexed_f64(properties.upper_critical, index)?,
+            });
+        }
+        Ok(readings)
+    }
+
+    fn optional_indexed_f64(
+        &self,
+        id: Option<NISysCfgIndexedProperty>,
+        index: u32,
+    ) -> Result<Option<f64>> {
+        match id {
+            Some(id) => {
+                optional_property(f64::read_resource_indexed_parameter(self.handle, id, index))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl Session {
+    /// Read the sensors of every chassis and controller, paired with the resource name.
+    pub fn sensor_readings(&self) -> Result<Vec<(String, SensorReadings)>> {
+        let query = HardwareQuery::from(FilterProperty::IsChassis(true))
+            .or(FilterProperty::ExpertName(ExpertType::NiController));
+
+        self.find_hardware_matching(&query)?
+            .iter()
+            .map(|hardware| Ok((hardware.name()?, hardware.sensors()?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::reading;
+
+    #[test]
+    fn test_readings_grouped_by_kind() {
+        let readings = SensorReadings {
+            temperature: vec![reading(SensorKind::Temperature, "CPU", 45.5)],
+            fan: vec![reading(SensorKind::Fan, "Fan 1", 1800.0)],
+            ..Default::default()
+        };
+
+        assert_eq!(readings.of_kind(SensorKind::Fan)[0].name, "Fan 1");
+        assert!(readings.of_kind(SensorKind::Voltage).is_empty());
+        let names: Vec<_> = readings
+            .iter()
+            .map(|reading| reading.name.as_str())
+            .collect();
+        assert_eq!(names, ["CPU", "Fan 1"]);
+        assert!(!readings.is_empty());
+    }
+
+    #[test]
+    fn test_empty_readings() {
+        assert!(SensorReadings::default().is_empty());
+        let only_current = SensorReadings {
+            current_temp: Some(30.0),
+            ..Default::default()
+        };
+        assert!(!only_current.is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            reading(SensorKind::Temperature, "CPU", 45.5).to_string(),
+            "CPU: 45.5 °C"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Services advertised by hardware resources.
+
+use ni_syscfg_sys::*;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::parameters::{ReadableParameter, ValueEnum};
+use crate::resources::HardwareResourceList;
+use crate::session::Session;
+
+/// A type of service which a resource provides or was discovered through.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, FromPrimitive)]
+pub enum ServiceType {
+    /// An NI device discovered over mDNS which uses the NI TCP protocol.
+    MdnsNiTcp = NISysCfgServiceType_NISysCfgServiceTypemDnsNiTcp,
+    /// An NI real-time target discovered over mDNS.
+    MdnsNiRealtime = NISysCfgServiceType_NISysCfgServiceTypemDnsNiRealtime,
+    /// A system running the NI system API discovered over mDNS.
+    MdnsNiSysapi = NISysCfgServiceType_NISysCfgServiceTypemDnsNiSysapi,
+    /// An NI web server discovered over mDNS.
+    MdnsNiHttp = NISysCfgServiceType_NISysCfgServiceTypemDnsNiHttp,
+    /// The local system.
+    LocalSystem = NISysCfgServiceType_NISysCfgServiceTypeLocalSystem,
+    /// A network interface on the local system.
+    LocalNetInterface = NISysCfgServiceType_NISysCfgServiceTypeLocalNetInterface,
+    /// A device which keeps time for the local system.
+    LocalTimeKeeper = NISysCfgServiceType_NISysCfgServiceTypeLocalTimeKeeper,
+    /// A device which can act as a time source for the local system.
+    LocalTimeSource = NISysCfgServiceType_NISysCfgServiceTypeLocalTimeSource,
+    /// An LXI instrument discovered over mDNS.
+    MdnsLxi = NISysCfgServiceType_NISysCfgServiceTypemDnsLxi,
+    /// An FPGA target on the local system.
+    LocalFpga = NISysCfgServiceType_NISysCfgServiceTypeLocalFpga,
+}
+
+impl ValueEnum for ServiceType {}
+
+impl HardwareResource {
+    /// The services which the resource advertises.
+    ///
+    /// Services which aren't in [ServiceType] are skipped so newer drivers don't break discovery.
+    pub fn services(&self) -> Result<Vec<ServiceType>> {
+        let count = self
+            .optional_parameter::<i32>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfServices,
+            )?
+            .unwrap_or(0);
+
+        let codes = (0..count.max(0) as u32)
+            .map(|index| {
+                i32::read_resource_indexed_parameter(
+                    self.handle,
+                    NISysCfgIndexedProperty_NISysCfgIndexedPropertyServiceType,
+                    index,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(known_services(codes))
+    }
+
+    /// Returns true if the resource advertises the `service`.
+    pub fn provides_service(&self, service: ServiceType) -> Result<bool> {
+        Ok(self.services()?.contains(&service))
+    }
+}
+
+fn known_services(codes: impl IntoIterator<Item = i32>) -> Vec<ServiceType> {
+    codes
+        .into_iter()
+        .filter_map(ServiceType::from_i32)
+        .collect()
+}
+
+impl Session {
+    /// Find the hardware resources which provide a service, e.g. all FPGA targets or time sources.
+    ///
+    /// This is a preset of [Session::find_hardware] with a filter on the service type.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::{SessionConfig, ServiceType};
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    ///
+    /// for hardware in session.find_hardware_by_service(ServiceType::LocalFpga).unwrap() {
+    ///   println!("Found FPGA target {}", hardware.name().unwrap())
+    /// }
+    /// ```
+    pub fn find_hardware_by_service(
+        &self,
+        service: ServiceType,
+    ) -> Result<HardwareResourceList<'_>> {
+        let mut filter = self.create_filter()?;
+        filter.set_service_type(service)?;
+        self.find_hardware(Some(&filter), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_type_from_api() {
+        assert_eq!(ServiceType::from_i32(0), Some(ServiceType::MdnsNiTcp));
+        assert_eq!(ServiceType::from_i32(8), Some(ServiceType::MdnsLxi));
+        assert_eq!(ServiceType::from_i32(9), Some(ServiceType::LocalFpga));
+        assert_eq!(ServiceType::from_i32(10), None);
+    }
+
+    #[test]
+    fn test_unknown_services_are_skipped() {
+        assert_eq!(
+            known_services([8, 42, 9, -1]),
+            [ServiceType::MdnsLxi, ServiceType::LocalFpga]
+        );
+    }
+}
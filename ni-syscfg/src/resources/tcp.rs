@@ -0,0 +1,214 @@
+//! Details of network attached instruments.
+
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use ni_syscfg_sys::*;
+use thiserror::Error;
+
+use super::{HardwareResource, ServiceType};
+use crate::error::Result;
+use crate::parameters::BusType;
+use crate::session::Session;
+
+/// A 48 bit Ethernet MAC address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MacAddress(pub [u8; 6]);
+
+/// The error returned when a string isn't a valid [MacAddress].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid MAC address: {0}")]
+pub struct ParseMacAddressError(String);
+
+impl FromStr for MacAddress {
+    type Err = ParseMacAddressError;
+
+    /// Parses six hex bytes separated by `:` or `-`, or twelve hex digits with no separator.
+    fn from_str(text: &str) -> std::result::Result<Self, Self::Err> {
+        let error = || ParseMacAddressError(text.to_string());
+        let text = text.trim();
+        let parts: Vec<&str> = if text.contains([':', '-']) {
+            text.split([':', '-']).collect()
+        } else if text.len() == 12 && text.is_ascii() {
+            (0..6)
+                .map(|index| &text[index * 2..index * 2 + 2])
+                .collect()
+        } else {
+            return Err(error());
+        };
+
+        if parts.len() != 6 {
+            return Err(error());
+        }
+        let mut bytes = [0u8; 6];
+        for (byte, part) in bytes.iter_mut().zip(parts) {
+            // from_str_radix accepts a sign, so check for exactly two hex digits first.
+            if part.len() != 2 || !part.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+                return Err(error());
+            }
+            *byte = u8::from_str_radix(part, 16).map_err(|_| error())?;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{a:02X}:{b:02X}:{c:02X}:{d:02X}:{e:02X}:{g:02X}")
+    }
+}
+
+/// The network details of a TCP/IP attached instrument.
+///
+/// Values which the instrument doesn't report, or which can't be parsed, are [None].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TcpInstrumentInfo {
+    pub host_name: Option<String>,
+    pub ip_address: Option<IpAddr>,
+    pub mac_address: Option<MacAddress>,
+    /// The class of the device as reported by the instrument, e.g. `LXI`.
+    pub device_class: Option<String>,
+}
+
+/// A network instrument found by [Session::lan_instruments].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LanInstrument {
+    pub name: String,
+    /// The services the instrument was discovered through.
+    pub services: Vec<ServiceType>,
+    pub info: TcpInstrumentInfo,
+}
+
+impl HardwareResource {
+    /// The network details of the instrument, or [None] if it doesn't report any.
+    pub fn tcp_instrument_info(&self) -> Result<Option<TcpInstrumentInfo>> {
+        let host_name =
+            self.optional_text(NISysCfgResourceProperty_NISysCfgResourcePropertyTcpHostName)?;
+        let ip_address = self
+            .optional_text(NISysCfgResourceProperty_NISysCfgResourcePropertyTcpIpAddress)?
+            .and_then(|address| address.parse().ok());
+        let mac_address = self
+            .optional_text(NISysCfgResourceProperty_NISysCfgResourcePropertyTcpMacAddress)?
+            .and_then(|address| address.parse().ok());
+        let device_class =
+            self.optional_text(NISysCfgResourceProperty_NISysCfgResourcePropertyTcpDeviceClass)?;
+
+        let info = TcpInstrumentInfo {
+            host_name,
+            ip_address,
+            mac_address,
+            device_class,
+        };
+        Ok(info.has_address().then_some(info))
+    }
+
+    /// Read a string property where a missing or empty value is [None].
+    fn optional_text(&self, id: NISysCfgResourceProperty) -> Result<Option<String>> {
+        Ok(self
+            .optional_parameter::<String>(id)?
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty()))
+    }
+}
+
+impl TcpInstrumentInfo {
+    fn has_address(&self) -> bool {
+        self.host_name.is_some() || self.ip_address.is_some() || self.mac_address.is_some()
+    }
+}
+
+/// The services which identify a resource as a network instrument.
+fn is_instrument_service(service: &ServiceType) -> bool {
+    matches!(service, ServiceType::MdnsLxi | ServiceType::MdnsNiTcp)
+}
+
+impl Session {
+    /// List the instruments on the network with their addresses.
+    ///
+    /// This includes resources discovered through the LXI or NI TCP mDNS services as well
+    /// as any resource connected over TCP/IP, and skips those which don't report an address.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::SessionConfig;
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    ///
+    /// for instrument in session.lan_instruments().unwrap() {
+    ///   println!("{} at {:?}", instrument.name, instrument.info.ip_address);
+    /// }
+    /// ```
+    pub fn lan_instruments(&self) -> Result<Vec<LanInstrument>> {
+        let mut instruments = Vec::new();
+        for hardware in self.find_hardware(None, None)? {
+            let services: Vec<ServiceType> = hardware
+                .services()?
+                .into_iter()
+                .filter(is_instrument_service)
+                .collect();
+            let on_tcp = matches!(
+                hardware.optional_parameter::<BusType>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyConnectsToBusType,
+                )?,
+                Some(BusType::TcpIp)
+            );
+            if services.is_empty() && !on_tcp {
+                continue;
+            }
+            if let Some(info) = hardware.tcp_instrument_info()? {
+                instruments.push(LanInstrument {
+                    name: hardware.name()?,
+                    services,
+                    info,
+                });
+            }
+        }
+        instruments.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(instruments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mac_address() {
+        let expected = MacAddress([0x00, 0x80, 0x2f, 0x1a, 0xb2, 0xff]);
+        assert_eq!("00:80:2F:1A:B2:FF".parse(), Ok(expected));
+        assert_eq!("00-80-2f-1a-b2-ff".parse(), Ok(expected));
+        assert_eq!("00802F1AB2FF".parse(), Ok(expected));
+        assert_eq!(expected.to_string(), "00:80:2F:1A:B2:FF");
+    }
+
+    #[test]
+    fn test_invalid_mac_address() {
+        assert!("00:80:2F:1A:B2".parse::<MacAddress>().is_err());
+        assert!("00:80:2F:1A:B2:FF:01".parse::<MacAddress>().is_err());
+        assert!("0:80:2F:1A:B2:FF".parse::<MacAddress>().is_err());
+        assert!("00:80:2F:1A:B2:GG".parse::<MacAddress>().is_err());
+        assert!("+f:00:00:00:00:00".parse::<MacAddress>().is_err());
+        assert!("+f0000000000".parse::<MacAddress>().is_err());
+        assert!("".parse::<MacAddress>().is_err());
+    }
+
+    #[test]
+    fn test_info_without_address() {
+        let info = TcpInstrumentInfo {
+            host_name: None,
+            ip_address: None,
+            mac_address: None,
+            device_class: Some("LXI".to_string()),
+        };
+        assert!(!info.has_address());
+    }
+
+    #[test]
+    fn test_instrument_services() {
+        assert!(is_instrument_service(&ServiceType::MdnsLxi));
+        assert!(is_instrument_service(&ServiceType::MdnsNiTcp));
+        assert!(!is_instrument_service(&ServiceType::LocalFpga));
+    }
+}
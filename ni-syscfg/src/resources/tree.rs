@@ -0,0 +1,334 @@
+//! Arranging the flat list of hardware resources into a chassis, slot and module tree.
+//!
+//! Resources which provide a bus publish a `ProvidesLinkName`. Resources plugged into that
+//! bus report the same name as their `ConnectsToLinkName`, along with the slot they are in.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use ni_syscfg_sys::*;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::parameters::BusType;
+use crate::session::Session;
+
+/// The hierarchy of hardware in the system as returned by [Session::hardware_tree].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HardwareTree {
+    /// The resources which aren't connected to any other resource in the system.
+    pub roots: Vec<HardwareNode>,
+}
+
+/// A hardware resource in the [HardwareTree] and the resources connected to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HardwareNode {
+    pub name: String,
+    /// The slot this resource is in on its parent, if it is in a slot.
+    pub slot: Option<i32>,
+    /// The number of slots this resource takes up on its parent.
+    pub slot_count: i32,
+    /// The type of bus this resource provides to the resources connected to it.
+    pub provides_bus_type: Option<BusType>,
+    /// The slots and resources connected to this resource.
+    ///
+    /// Where the resource has a known number of slots, every slot is listed in order with
+    /// empty slots included.
+    pub children: Vec<HardwareTreeEntry>,
+}
+
+/// An entry under a [HardwareNode] in the tree.
+#[derive(Clone, Debug, PartialEq)]
+pub enum HardwareTreeEntry {
+    Resource(HardwareNode),
+    /// A slot with nothing in it.
+    EmptySlot(i32),
+}
+
+impl HardwareNode {
+    /// The first and last slot this resource occupies on its parent.
+    pub fn slot_range(&self) -> Option<(i32, i32)> {
+        self.slot
+            .map(|first| (first, first + self.slot_count.max(1) - 1))
+    }
+}
+
+/// The link properties of a single resource used to build the tree.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ResourceLinks {
+    name: String,
+    provides_link_name: Option<String>,
+    provides_bus_type: Option<BusType>,
+    connects_to_link_name: Option<String>,
+    slot_number: Option<i32>,
+    number_of_slots: Option<i32>,
+    connects_to_num_slots: Option<i32>,
+    slot_offset_left: Option<i32>,
+}
+
+impl HardwareResource {
+    fn links(&self) -> Result<ResourceLinks> {
+        Ok(ResourceLinks {
+            name: self.name()?,
+            provides_link_name: self
+                .optional_parameter::<String>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyProvidesLinkName,
+                )?
+                .filter(|name| !name.is_empty()),
+            provides_bus_type: self.optional_parameter::<BusType>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyProvidesBusType,
+            )?,
+            connects_to_link_name: self
+                .optional_parameter::<String>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyConnectsToLinkName,
+                )?
+                .filter(|name| !name.is_empty()),
+            slot_number: self.slot_number()?,
+            number_of_slots: self.optional_parameter::<i32>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyNumberOfSlots,
+            )?,
+            connects_to_num_slots: self.optional_parameter::<i32>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertyConnectsToNumSlots,
+            )?,
+            slot_offset_left: self.optional_parameter::<i32>(
+                NISysCfgResourceProperty_NISysCfgResourcePropertySlotOffsetLeft,
+            )?,
+        })
+    }
+}
+
+impl Session {
+    /// Arrange the hardware in the system into a tree of chassis, slots and modules.
+    ///
+    /// The tree is a snapshot which doesn't hold any handles to the hardware.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::SessionConfig;
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    ///
+    /// println!("{}", session.hardware_tree().unwrap());
+    /// ```
+    pub fn hardware_tree(&self) -> Result<HardwareTree> {
+        let resources = self
+            .find_hardware(None, None)?
+            .map(|hardware| hardware.links())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(build_tree(resources))
+    }
+}
+
+fn build_tree(resources: Vec<ResourceLinks>) -> HardwareTree {
+    let provided: HashSet<&str> = resources
+        .iter()
+        .filter_map(|resource| resource.provides_link_name.as_deref())
+        .collect();
+
+    let mut visited = vec![false; resources.len()];
+    let mut roots: Vec<HardwareNode> = (0..resources.len())
+        .filter(|&index| {
+            !resources[index]
+                .connects_to_link_name
+                .as_deref()
+                .is_some_and(|link| provided.contains(link))
+        })
+        .map(|index| build_node(&resources, index, &mut visited))
+        .collect();
+
+    // Anything left over is part of a loop of links, so add it at the top level rather than lose it.
+    for index in 0..resources.len() {
+        if !visited[index] {
+            roots.push(build_node(&resources, index, &mut visited));
+        }
+    }
+
+    roots.sort_by(|a, b| a.name.cmp(&b.name));
+    HardwareTree { roots }
+}
+
+fn build_node(resources: &[ResourceLinks], index: usize, visited: &mut [bool]) -> HardwareNode {
+    visited[index] = true;
+    let resource = &resources[index];
+
+    let mut children = Vec::new();
+    if let Some(link) = &resource.provides_link_name {
+        for child in 0..resources.len() {
+            // Checked as we go as building a sibling may already have reached this child.
+            if !visited[child] && resources[child].connects_to_link_name.as_ref() == Some(link) {
+                children.push(build_node(resources, child, visited));
+            }
+        }
+    }
+    children.sort_by(|a, b| {
+        (a.slot.is_none(), a.slot, &a.name).cmp(&(b.slot.is_none(), b.slot, &b.name))
+    });
+
+    HardwareNode {
+        name: resource.name.clone(),
+        // The slot number is the slot of the connector, which may not be the leftmost slot occupied.
+        slot: resource
+            .slot_number
+            .map(|slot| slot - resource.slot_offset_left.unwrap_or(0).max(0)),
+        slot_count: resource.connects_to_num_slots.unwrap_or(1).max(1),
+        provides_bus_type: resource.provides_bus_type,
+        children: with_empty_slots(children, resource.number_of_slots.unwrap_or(0)),
+    }
+}
+
+/// Lay out `children`, which are sorted by slot, across the slots with the gaps marked as empty.
+fn with_empty_slots(children: Vec<HardwareNode>, number_of_slots: i32) -> Vec<HardwareTreeEntry> {
+    let mut entries = Vec::with_capacity(children.len());
+    let mut next_slot = 1;
+
+    for child in children {
+        if let Some((first, last)) = child.slot_range() {
+            while next_slot < first && next_slot <= number_of_slots {
+                entries.push(HardwareTreeEntry::EmptySlot(next_slot));
+                next_slot += 1;
+            }
+            next_slot = next_slot.max(last + 1);
+        }
+        entries.push(HardwareTreeEntry::Resource(child));
+    }
+
+    // Children without a slot are sorted last so this is the end of the slotted children.
+    let unslotted_start = entries
+        .iter()
+        .position(|entry| matches!(entry, HardwareTreeEntry::Resource(node) if node.slot.is_none()))
+        .unwrap_or(entries.len());
+    let trailing_empty: Vec<_> = (next_slot..=number_of_slots)
+        .map(HardwareTreeEntry::EmptySlot)
+        .collect();
+    entries.splice(unslotted_start..unslotted_start, trailing_empty);
+    entries
+}
+
+impl fmt::Display for HardwareTree {
+    /// Prints the tree with each level indented, one resource or empty slot per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for root in &self.roots {
+            root.write_indented(f, 0)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for HardwareNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_indented(f, 0)
+    }
+}
+
+impl HardwareNode {
+    fn write_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+        match self.slot_range() {
+            Some((first, last)) if first != last => {
+                writeln!(f, "{indent}Slots {first}-{last}: {}", self.name)?
+            }
+            Some((first, _)) => writeln!(f, "{indent}Slot {first}: {}", self.name)?,
+            None => writeln!(f, "{indent}{}", self.name)?,
+        }
+        for child in &self.children {
+            match child {
+                HardwareTreeEntry::Resource(node) => node.write_indented(f, depth + 1)?,
+                HardwareTreeEntry::EmptySlot(slot) => {
+                    writeln!(f, "{indent}  Slot {slot}: (empty)")?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chassis(name: &str, link: &str, slots: i32) -> ResourceLinks {
+        ResourceLinks {
+            name: name.to_string(),
+            provides_link_name: Some(link.to_string()),
+            provides_bus_type: Some(BusType::PciPxi),
+            number_of_slots: Some(slots),
+            ..Default::default()
+        }
+    }
+
+    fn module(name: &str, link: &str, slot: i32) -> ResourceLinks {
+        ResourceLinks {
+            name: name.to_string(),
+            connects_to_link_name: Some(link.to_string()),
+            slot_number: Some(slot),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_modules_are_placed_in_slots() {
+        let tree = build_tree(vec![
+            module("PXI1Slot4", "PXI1", 4),
+            chassis("PXI1Chassis1", "PXI1", 4),
+            module("PXI1Slot2", "PXI1", 2),
+        ]);
+
+        assert_eq!(tree.roots.len(), 1);
+        assert_eq!(
+            tree.to_string(),
+            "PXI1Chassis1\n  Slot 1: (empty)\n  Slot 2: PXI1Slot2\n  Slot 3: (empty)\n  Slot 4: PXI1Slot4\n"
+        );
+    }
+
+    #[test]
+    fn test_multi_slot_module_covers_slots() {
+        let mut wide = module("PXI1Slot3", "PXI1", 3);
+        wide.connects_to_num_slots = Some(2);
+        wide.slot_offset_left = Some(1);
+        let tree = build_tree(vec![chassis("PXI1Chassis1", "PXI1", 4), wide]);
+
+        assert_eq!(
+            tree.to_string(),
+            "PXI1Chassis1\n  Slot 1: (empty)\n  Slots 2-3: PXI1Slot3\n  Slot 4: (empty)\n"
+        );
+    }
+
+    #[test]
+    fn test_nested_and_unslotted_resources() {
+        let mut cdaq = module("cDAQ1", "PXI1", 1);
+        cdaq.provides_link_name = Some("cDAQ1Bus".to_string());
+        cdaq.number_of_slots = Some(2);
+        let tree = build_tree(vec![
+            chassis("PXI1Chassis1", "PXI1", 2),
+            cdaq,
+            module("cDAQ1Mod2", "cDAQ1Bus", 2),
+            ResourceLinks {
+                name: "PXI1Controller".to_string(),
+                connects_to_link_name: Some("PXI1".to_string()),
+                ..Default::default()
+            },
+            ResourceLinks {
+                name: "GPIB0".to_string(),
+                connects_to_link_name: Some("Missing".to_string()),
+                ..Default::default()
+            },
+        ]);
+
+        assert_eq!(
+            tree.to_string(),
+            "GPIB0\nPXI1Chassis1\n  Slot 1: cDAQ1\n    Slot 1: (empty)\n    Slot 2: cDAQ1Mod2\n  Slot 2: (empty)\n  PXI1Controller\n"
+        );
+    }
+
+    #[test]
+    fn test_link_loops_are_kept() {
+        let mut a = module("A", "B", 1);
+        a.provides_link_name = Some("A".to_string());
+        let mut b = module("B", "A", 1);
+        b.provides_link_name = Some("B".to_string());
+        let tree = build_tree(vec![a, b]);
+
+        assert_eq!(tree.roots.len(), 1);
+        assert_eq!(tree.to_string(), "Slot 1: A\n  Slot 1: B\n");
+    }
+}
@@ -0,0 +1,206 @@
+//! Building VISA resource strings from the addressing properties of a resource.
+
+use ni_syscfg_sys::*;
+
+use super::HardwareResource;
+use crate::error::Result;
+use crate::parameters::BusType;
+
+impl HardwareResource {
+    /// The VISA resource string for an instrument, such as `GPIB0::5::INSTR` or `TCPIP::10.0.0.2::INSTR`.
+    ///
+    /// This is built from the addressing properties for GPIB, TCP/IP, serial and USB instruments.
+    /// Returns [None] for other buses or where the properties needed aren't available.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::SessionConfig;
+    ///
+    /// let session = SessionConfig::new().connect().unwrap();
+    ///
+    /// for hardware in session.find_hardware(None, None).unwrap() {
+    ///   if let Some(resource) = hardware.visa_resource_string().unwrap() {
+    ///     println!("{} is {resource}", hardware.name().unwrap());
+    ///   }
+    /// }
+    /// ```
+    pub fn visa_resource_string(&self) -> Result<Option<String>> {
+        let bus_type = self.optional_parameter::<BusType>(
+            NISysCfgResourceProperty_NISysCfgResourcePropertyConnectsToBusType,
+        )?;
+
+        Ok(match bus_type {
+            Some(BusType::Gpib) => {
+                let primary = self.optional_parameter::<i32>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyGpibPrimaryAddress,
+                )?;
+                let secondary = self.optional_parameter::<i32>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyGpibSecondaryAddress,
+                )?;
+                primary.and_then(|primary| gpib_resource(primary, secondary))
+            }
+            Some(BusType::TcpIp) => {
+                let host_name = self.optional_parameter::<String>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyTcpHostName,
+                )?;
+                let ip_address = self.optional_parameter::<String>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyTcpIpAddress,
+                )?;
+                tcpip_resource(host_name.as_deref(), ip_address.as_deref())
+            }
+            Some(BusType::Serial) => self
+                .optional_parameter::<String>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertySerialPortBinding,
+                )?
+                .and_then(|binding| asrl_resource(&binding)),
+            Some(BusType::Usb) => {
+                let vendor_id = self.optional_parameter::<u32>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyVendorId,
+                )?;
+                let product_id = self.optional_parameter::<u32>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyProductId,
+                )?;
+                let serial_number = self.optional_parameter::<String>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertySerialNumber,
+                )?;
+                let interface = self.optional_parameter::<u16>(
+                    NISysCfgResourceProperty_NISysCfgResourcePropertyUsbInterface,
+                )?;
+                match (vendor_id, product_id, serial_number) {
+                    (Some(vendor_id), Some(product_id), Some(serial_number)) => {
+                        usb_resource(vendor_id, product_id, &serial_number, interface)
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+    }
+}
+
+/// GPIB secondary addresses may be reported in the IEEE 488 form of 96 to 126 rather than 0 to 30.
+const GPIB_SECONDARY_OFFSET: i32 = 96;
+
+fn gpib_resource(primary: i32, secondary: Option<i32>) -> Option<String> {
+    if !(0..=30).contains(&primary) {
+        return None;
+    }
+    let secondary = secondary
+        .map(|address| {
+            if address >= GPIB_SECONDARY_OFFSET {
+                address - GPIB_SECONDARY_OFFSET
+            } else {
+                address
+            }
+        })
+        .filter(|address| (0..=30).contains(address));
+
+    Some(match secondary {
+        Some(secondary) => format!("GPIB0::{primary}::{secondary}::INSTR"),
+        None => format!("GPIB0::{primary}::INSTR"),
+    })
+}
+
+fn tcpip_resource(host_name: Option<&str>, ip_address: Option<&str>) -> Option<String> {
+    let host = [host_name, ip_address]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|host| !host.is_empty())?;
+    Some(format!("TCPIP::{host}::INSTR"))
+}
+
+/// VISA numbers serial ports from 1, matching `COM<n>` on Windows and `/dev/ttyS<n-1>` on Linux.
+fn asrl_resource(port_binding: &str) -> Option<String> {
+    let port_binding = port_binding.trim();
+    let number = if let Some(number) = strip_prefix_ignore_case(port_binding, "COM") {
+        number.parse::<u32>().ok().filter(|&number| number > 0)?
+    } else if let Some(number) = port_binding.strip_prefix("/dev/ttyS") {
+        number.parse::<u32>().ok()? + 1
+    } else {
+        return None;
+    };
+    Some(format!("ASRL{number}::INSTR"))
+}
+
+fn usb_resource(
+    vendor_id: u32,
+    product_id: u32,
+    serial_number: &str,
+    interface: Option<u16>,
+) -> Option<String> {
+    let serial_number = serial_number.trim();
+    if serial_number.is_empty() {
+        return None;
+    }
+    Some(match interface.filter(|&interface| interface != 0) {
+        Some(interface) => format!(
+            "USB0::0x{vendor_id:04X}::0x{product_id:04X}::{serial_number}::{interface}::INSTR"
+        ),
+        None => format!("USB0::0x{vendor_id:04X}::0x{product_id:04X}::{serial_number}::INSTR"),
+    })
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &text[prefix.len()..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpib_resource() {
+        assert_eq!(gpib_resource(5, None).as_deref(), Some("GPIB0::5::INSTR"));
+        assert_eq!(
+            gpib_resource(5, Some(2)).as_deref(),
+            Some("GPIB0::5::2::INSTR")
+        );
+        assert_eq!(
+            gpib_resource(5, Some(98)).as_deref(),
+            Some("GPIB0::5::2::INSTR")
+        );
+        assert_eq!(
+            gpib_resource(5, Some(-1)).as_deref(),
+            Some("GPIB0::5::INSTR")
+        );
+        assert_eq!(gpib_resource(31, None), None);
+    }
+
+    #[test]
+    fn test_tcpip_resource() {
+        assert_eq!(
+            tcpip_resource(Some("scope1"), Some("10.0.0.2")).as_deref(),
+            Some("TCPIP::scope1::INSTR")
+        );
+        assert_eq!(
+            tcpip_resource(Some(""), Some("10.0.0.2")).as_deref(),
+            Some("TCPIP::10.0.0.2::INSTR")
+        );
+        assert_eq!(tcpip_resource(None, None), None);
+    }
+
+    #[test]
+    fn test_asrl_resource() {
+        assert_eq!(asrl_resource("COM3").as_deref(), Some("ASRL3::INSTR"));
+        assert_eq!(asrl_resource("com1").as_deref(), Some("ASRL1::INSTR"));
+        assert_eq!(asrl_resource("/dev/ttyS0").as_deref(), Some("ASRL1::INSTR"));
+        assert_eq!(asrl_resource("COM0"), None);
+        assert_eq!(asrl_resource("/dev/ttyUSB0"), None);
+    }
+
+    #[test]
+    fn test_usb_resource() {
+        assert_eq!(
+            usb_resource(0x3923, 0x7514, "01ABC123", None).as_deref(),
+            Some("USB0::0x3923::0x7514::01ABC123::INSTR")
+        );
+        assert_eq!(
+            usb_resource(0x3923, 0x7514, "01ABC123", Some(1)).as_deref(),
+            Some("USB0::0x3923::0x7514::01ABC123::1::INSTR")
+        );
+        assert_eq!(usb_resource(0x3923, 0x7514, "", None), None);
+    }
+}
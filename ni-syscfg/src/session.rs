@@ -2,12 +2,12 @@ use ni_syscfg_sys::*;
 use std::ffi::CString;
 use std::time::Duration;
 
-use crate::error::{api_status, Result};
+use crate::error::{api_status, NiSystemConfigurationError, Result};
 use crate::experts::ExpertType;
 use crate::handles::close_handle;
 use crate::hardware_filter::{FilterMode, HardwareFilter};
 use crate::parameters::ApiBool;
-use crate::resources::HardwareResourceList;
+use crate::resources::{HardwareResource, HardwareResourceList};
 
 #[repr(i32)]
 #[derive(Clone, Copy, Debug)]
@@ -28,6 +28,7 @@ pub struct SessionConfig<'a> {
     locale: Locale,
     force_refresh: bool,
     timeout: Duration,
+    reject_simulated: bool,
 }
 
 impl<'a> SessionConfig<'a> {
@@ -39,6 +40,7 @@ impl<'a> SessionConfig<'a> {
             locale: Locale::Default,
             force_refresh: false,
             timeout: Duration::from_secs(1),
+            reject_simulated: false,
         }
     }
 
@@ -72,6 +74,14 @@ impl<'a> SessionConfig<'a> {
         self
     }
 
+    /// Make [Session::find_hardware] return an error if it finds any simulated devices.
+    ///
+    /// Use this in production code to guarantee it never runs against simulated hardware by accident.
+    pub fn reject_simulated(mut self, reject_simulated: bool) -> Self {
+        self.reject_simulated = reject_simulated;
+        self
+    }
+
     pub fn connect(&self) -> Result<Session> {
         fn optional_cstring_to_ptr(input: &Option<CString>) -> *const i8 {
             if let Some(inner) = input {
@@ -100,7 +110,9 @@ impl<'a> SessionConfig<'a> {
             ))?;
         }
 
-        Ok(Session::new_from_handle(handle))
+        let mut session = Session::new_from_handle(handle);
+        session.reject_simulated = self.reject_simulated;
+        Ok(session)
     }
 }
 
@@ -110,17 +122,26 @@ impl<'a> SessionConfig<'a> {
 /// and allows you to access hardware and software resources through the API.
 pub struct Session {
     handle: NISysCfgSessionHandle,
+    reject_simulated: bool,
 }
 
 impl Session {
     fn new_from_handle(handle: NISysCfgSessionHandle) -> Self {
-        Self { handle }
+        Self {
+            handle,
+            reject_simulated: false,
+        }
     }
 
     pub(crate) fn handle(&self) -> NISysCfgSessionHandle {
         self.handle
     }
 
+    /// True if the session was configured with [SessionConfig::reject_simulated].
+    pub(crate) fn rejects_simulated(&self) -> bool {
+        self.reject_simulated
+    }
+
     /// Create a new filter for the session to use as part of [find_hardware]
     pub fn create_filter(&self) -> Result<HardwareFilter> {
         HardwareFilter::new(&self)
@@ -155,6 +176,10 @@ impl Session {
     /// }
     /// ```
     ///
+    /// If the session was configured with [SessionConfig::reject_simulated] this returns
+    /// [NiSystemConfigurationError::SimulatedDeviceRejected] naming the first simulated device found.
+    /// The results are read in full to check them before the list is returned.
+    ///
     /// # Example With DAQmx Expert Filter
     /// ```
     /// use ni_syscfg::{SessionConfig, ExpertType};
@@ -170,34 +195,113 @@ impl Session {
         filtering: Option<&HardwareFilter>,
         experts: Option<&[ExpertType]>,
     ) -> Result<HardwareResourceList> {
-        let mut list_handle: NISysCfgEnumResourceHandle = std::ptr::null_mut();
-
-        let (filter_mode, filter_handle) = if let Some(filter) = filtering {
-            (filter.mode(), filter.handle())
-        } else {
-            (
-                FilterMode::MatchValuesAll,
-                std::ptr::null_mut() as NISysCfgFilterHandle,
-            )
-        };
-
-        let expert_list = if let Some(list) = experts {
-            expert_list_to_text(list)?
-        } else {
-            CString::new("")?
-        };
+        find_hardware_guarded(self.handle, self.reject_simulated, filtering, experts)
+    }
 
-        unsafe {
-            api_status(NISysCfgFindHardware(
-                self.handle,
-                filter_mode as i32,
-                filter_handle,
-                expert_list.as_ptr(),
-                &mut list_handle,
-            ))?;
+    /// Find only the physical hardware resources, skipping any simulated devices.
+    ///
+    /// This takes the same arguments as [Session::find_hardware].
+    pub fn find_real_hardware(
+        &self,
+        filtering: Option<&HardwareFilter>,
+        experts: Option<&[ExpertType]>,
+    ) -> Result<Vec<HardwareResource>> {
+        self.find_hardware_by_simulation(filtering, experts, false)
+    }
+
+    /// Find only the simulated hardware resources.
+    ///
+    /// This takes the same arguments as [Session::find_hardware].
+    pub fn find_simulated_hardware(
+        &self,
+        filtering: Option<&HardwareFilter>,
+        experts: Option<&[ExpertType]>,
+    ) -> Result<Vec<HardwareResource>> {
+        self.find_hardware_by_simulation(filtering, experts, true)
+    }
+
+    fn find_hardware_by_simulation(
+        &self,
+        filtering: Option<&HardwareFilter>,
+        experts: Option<&[ExpertType]>,
+        simulated: bool,
+    ) -> Result<Vec<HardwareResource>> {
+        // Not using find_hardware so listing real devices still works with the simulated device guard.
+        let list_handle = find_hardware_handle(self.handle, filtering, experts)?;
+        let mut found = Vec::new();
+        for hardware in HardwareResourceList::from_handle(list_handle, self) {
+            if hardware.is_simulated()? == simulated {
+                found.push(hardware);
+            }
         }
+        Ok(found)
+    }
+}
 
-        Ok(HardwareResourceList::from_handle(list_handle, self))
+/// Start a hardware search on the session handle and return the handle to the results.
+///
+/// This is shared with the other session types which only hold the session handle.
+pub(crate) fn find_hardware_handle(
+    session_handle: NISysCfgSessionHandle,
+    filtering: Option<&HardwareFilter>,
+    experts: Option<&[ExpertType]>,
+) -> Result<NISysCfgEnumResourceHandle> {
+    let mut list_handle: NISysCfgEnumResourceHandle = std::ptr::null_mut();
+
+    let (filter_mode, filter_handle) = if let Some(filter) = filtering {
+        (filter.mode(), filter.handle())
+    } else {
+        (
+            FilterMode::MatchValuesAll,
+            std::ptr::null_mut() as NISysCfgFilterHandle,
+        )
+    };
+
+    let expert_list = if let Some(list) = experts {
+        expert_list_to_text(list)?
+    } else {
+        CString::new("")?
+    };
+
+    unsafe {
+        api_status(NISysCfgFindHardware(
+            session_handle,
+            filter_mode as i32,
+            filter_handle,
+            expert_list.as_ptr(),
+            &mut list_handle,
+        ))?;
+    }
+
+    Ok(list_handle)
+}
+
+/// Start a hardware search, applying the simulated device guard if `reject_simulated` is set.
+///
+/// The caller must make sure the list doesn't outlive the session.
+pub(crate) fn find_hardware_guarded<'a>(
+    session_handle: NISysCfgSessionHandle,
+    reject_simulated: bool,
+    filtering: Option<&HardwareFilter>,
+    experts: Option<&[ExpertType]>,
+) -> Result<HardwareResourceList<'a>> {
+    let list_handle = find_hardware_handle(session_handle, filtering, experts)?;
+    let list = HardwareResourceList::from_session_handle(list_handle, session_handle);
+    if !reject_simulated {
+        return Ok(list);
+    }
+    // Check and return the same results so nothing can appear between the two.
+    list.read_ahead(HardwareResource::reject_if_simulated)
+}
+
+impl HardwareResource {
+    fn reject_if_simulated(&self) -> Result<()> {
+        if self.is_simulated()? {
+            return Err(NiSystemConfigurationError::SimulatedDeviceRejected(
+                self.name()?,
+            ));
+        }
+        Ok(())
     }
 }
 
@@ -222,6 +326,18 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn simulated_guard_is_opt_in() {
+        assert!(!SessionConfig::new().reject_simulated);
+        assert!(SessionConfig::new().reject_simulated(true).reject_simulated);
+    }
+
+    #[test]
+    fn simulated_guard_error_names_resource() {
+        let error = NiSystemConfigurationError::SimulatedDeviceRejected("SimDev1".to_string());
+        assert!(error.to_string().contains("SimDev1"));
+    }
+
     #[test]
     fn expert_list_to_string() {
         //use a list of unknown so we know what it will produce.
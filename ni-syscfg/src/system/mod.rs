@@ -2,12 +2,20 @@
 
 mod real_time;
 mod network;
+mod summary;
+mod usage;
 
 use ni_syscfg_sys::{NISysCfgSystemProperty, NISysCfgSystemProperty_NISysCfgSystemPropertyHostname, NISysCfgSystemProperty_NISysCfgSystemPropertyIsLocked, NISysCfgSystemProperty_NISysCfgSystemPropertyIsLockingSupported, NISysCfgSystemProperty_NISysCfgSystemPropertyProductId, NISysCfgSystemProperty_NISysCfgSystemPropertyProductName, NISysCfgSystemProperty_NISysCfgSystemPropertySerialNumber};
+use std::ffi::c_void;
+use std::ptr::null_mut;
+use ni_syscfg_sys::{NISysCfgGetSystemProperty, NISysCfgResourceHandle, NISysCfgSystemProperty_NISysCfgSystemPropertySystemResourceHandle};
 use crate::Session;
 use crate::parameters::{ApiBool, ReadableParameter};
-use crate::error::Result;
-pub use real_time::RealTimeSession;
+use crate::error::{api_status, Result};
+use crate::resources::HardwareResource;
+pub use real_time::{RealTimeSession, SlotProgramMode};
+pub use summary::{ControllerMemory, SystemSummary};
+pub use usage::{MemoryUsage, Usage, UsageLimit};
 
 impl Session {
 
@@ -35,5 +43,14 @@ impl Session {
         i32::read_system_parameter(self.handle(), NISysCfgSystemProperty_NISysCfgSystemPropertyProductId)
     }
 
+    /// The resource which represents the system itself, such as the controller of a real-time target.
+    pub fn system_resource(&self) -> Result<HardwareResource> {
+        let mut handle: NISysCfgResourceHandle = null_mut();
+        unsafe {
+            api_status(NISysCfgGetSystemProperty(self.handle(), NISysCfgSystemProperty_NISysCfgSystemPropertySystemResourceHandle, &mut handle as *mut _ as *mut c_void))?;
+        }
+        Ok(HardwareResource::from_handle(handle))
+    }
+
 
 }
\ No newline at end of file
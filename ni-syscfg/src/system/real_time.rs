@@ -4,20 +4,49 @@ use ni_syscfg_sys::{NISysCfgSessionHandle, NISysCfgSystemProperty_NISysCfgSystem
 use crate::Session;
 use crate::error::Result;
 use crate::parameters::ReadableParameter;
+use crate::resources::ModuleProgramMode;
+use crate::session::find_hardware_guarded;
 
 pub struct RealTimeSession {
-    handle: NISysCfgSessionHandle
+    handle: NISysCfgSessionHandle,
+    reject_simulated: bool
+}
+
+/// The program mode of the module in a single slot, as returned by [RealTimeSession::module_modes].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotProgramMode {
+    /// The slot the module is in, if it reports one.
+    pub slot: Option<i32>,
+    pub name: String,
+    pub mode: ModuleProgramMode,
 }
 
 
 impl RealTimeSession {
+    /// Use the real-time functions of the `session`, keeping its [crate::SessionConfig::reject_simulated] setting.
     pub fn from_session(session: &Session) -> RealTimeSession {
         Self {
-            handle: session.handle()
+            handle: session.handle(),
+            reject_simulated: session.rejects_simulated()
         }
     }
 
     pub fn status(&self) -> Result<String> {
         String::read_system_parameter(self.handle, NISysCfgSystemProperty_NISysCfgSystemPropertySystemState)
     }
-}
\ No newline at end of file
+
+    /// The program mode of each module in the system which has one, sorted by slot.
+    ///
+    /// Like [Session::find_hardware], this fails if the session rejects simulated devices and one is found.
+    pub fn module_modes(&self) -> Result<Vec<SlotProgramMode>> {
+        let list = find_hardware_guarded(self.handle, self.reject_simulated, None, None)?;
+        let mut modes = Vec::new();
+        for hardware in list {
+            if let Some(mode) = hardware.module_program_mode()? {
+                modes.push(SlotProgramMode { slot: hardware.slot_number()?, name: hardware.name()?, mode });
+            }
+        }
+        modes.sort_by_key(|module| (module.slot.is_none(), module.slot));
+        Ok(modes)
+    }
+}
@@ -0,0 +1,134 @@
+//! A summary of the system for inventory and auditing.
+
+use std::fmt;
+
+use crate::error::Result;
+use crate::resources::MemoryModule;
+use crate::types::BYTES_PER_MEGABYTE;
+use crate::Session;
+
+/// An overview of the system and its controllers, as returned by [Session::system_summary].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemSummary {
+    pub hostname: String,
+    pub product_name: String,
+    pub serial_number: String,
+    /// The controllers in the system which report their memory modules.
+    pub controllers: Vec<ControllerMemory>,
+}
+
+/// The memory installed in a single controller.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ControllerMemory {
+    pub name: String,
+    pub memory_modules: Vec<MemoryModule>,
+}
+
+impl ControllerMemory {
+    /// The combined capacity of the memory modules in bytes.
+    pub fn total_bytes(&self) -> u64 {
+        self.memory_modules
+            .iter()
+            .map(|module| module.capacity_bytes)
+            .sum()
+    }
+}
+
+impl SystemSummary {
+    /// The combined capacity of the memory modules in all controllers in bytes.
+    pub fn total_memory_bytes(&self) -> u64 {
+        self.controllers
+            .iter()
+            .map(ControllerMemory::total_bytes)
+            .sum()
+    }
+}
+
+impl Session {
+    /// Collect a summary of the system, including the memory modules of each controller.
+    pub fn system_summary(&self) -> Result<SystemSummary> {
+        let mut controllers = Vec::new();
+        for hardware in self.find_hardware(None, None)? {
+            let memory_modules = hardware.memory_modules()?;
+            if !memory_modules.is_empty() {
+                controllers.push(ControllerMemory {
+                    name: hardware.name()?,
+                    memory_modules,
+                });
+            }
+        }
+
+        Ok(SystemSummary {
+            hostname: self.hostname()?,
+            product_name: self.product_name()?,
+            serial_number: self.serial_number()?,
+            controllers,
+        })
+    }
+}
+
+impl fmt::Display for SystemSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({})", self.hostname, self.product_name)?;
+        writeln!(f, "Serial Number: {}", self.serial_number)?;
+        for controller in &self.controllers {
+            writeln!(
+                f,
+                "{}: {} MB",
+                controller.name,
+                controller.total_bytes() / BYTES_PER_MEGABYTE
+            )?;
+            for module in &controller.memory_modules {
+                writeln!(
+                    f,
+                    "  {}: {} {} MB",
+                    module.location,
+                    module.model,
+                    module.capacity_bytes / BYTES_PER_MEGABYTE
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module(location: &str, megabytes: u64) -> MemoryModule {
+        MemoryModule {
+            location: location.to_string(),
+            model: "DDR4".to_string(),
+            capacity_bytes: megabytes * BYTES_PER_MEGABYTE,
+        }
+    }
+
+    fn summary() -> SystemSummary {
+        SystemSummary {
+            hostname: "pxi-controller".to_string(),
+            product_name: "PXIe-8861".to_string(),
+            serial_number: "01ABC123".to_string(),
+            controllers: vec![ControllerMemory {
+                name: "PXI1Slot1".to_string(),
+                memory_modules: vec![module("DIMM0", 8192), module("DIMM1", 8192)],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_total_memory() {
+        assert_eq!(
+            summary().total_memory_bytes(),
+            16 * 1024 * BYTES_PER_MEGABYTE
+        );
+    }
+
+    #[test]
+    fn test_summary_text() {
+        assert_eq!(
+            summary().to_string(),
+            "pxi-controller (PXIe-8861)\nSerial Number: 01ABC123\nPXI1Slot1: 16384 MB\n  DIMM0: DDR4 8192 MB\n  DIMM1: DDR4 8192 MB\n"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Memory and disk usage of the system.
+
+use ni_syscfg_sys::*;
+
+use crate::error::Result;
+use crate::parameters::{optional_property, ReadableParameter};
+use crate::Session;
+
+/// How much of a resource such as memory or disk space is in use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Usage {
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+}
+
+impl Usage {
+    pub fn used_bytes(&self) -> u64 {
+        self.total_bytes.saturating_sub(self.free_bytes)
+    }
+
+    /// The percentage in use, from 0 to 100. This is 0 if the total is unknown.
+    pub fn utilization(&self) -> f64 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+        self.used_bytes() as f64 / self.total_bytes as f64 * 100.0
+    }
+
+    /// Returns true if the usage is past the `limit`.
+    pub fn exceeds(&self, limit: UsageLimit) -> bool {
+        match limit {
+            UsageLimit::MaxUtilization(percent) => self.utilization() > percent,
+            UsageLimit::MinFreeBytes(bytes) => self.free_bytes < bytes,
+        }
+    }
+}
+
+/// A threshold for [Usage::exceeds].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UsageLimit {
+    /// The highest acceptable utilization as a percentage.
+    MaxUtilization(f64),
+    /// The least acceptable free space in bytes.
+    MinFreeBytes(u64),
+}
+
+/// The memory usage of the system as returned by [Session::memory].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub physical: Usage,
+    /// Virtual memory, where the system reports it.
+    pub virtual_memory: Option<Usage>,
+    /// The largest contiguous block of free memory in bytes, where the system reports it.
+    ///
+    /// On real-time targets this can be much smaller than the free memory due to fragmentation.
+    pub largest_free_block: Option<u64>,
+}
+
+impl Session {
+    /// The memory usage of the system.
+    pub fn memory(&self) -> Result<MemoryUsage> {
+        let virtual_memory = match (
+            self.optional_bytes(NISysCfgSystemProperty_NISysCfgSystemPropertyMemoryVirtTotal)?,
+            self.optional_bytes(NISysCfgSystemProperty_NISysCfgSystemPropertyMemoryVirtFree)?,
+        ) {
+            (Some(total_bytes), Some(free_bytes)) => Some(Usage {
+                total_bytes,
+                free_bytes,
+            }),
+            _ => None,
+        };
+
+        Ok(MemoryUsage {
+            physical: Usage {
+                total_bytes: self
+                    .bytes(NISysCfgSystemProperty_NISysCfgSystemPropertyMemoryPhysTotal)?,
+                free_bytes: self
+                    .bytes(NISysCfgSystemProperty_NISysCfgSystemPropertyMemoryPhysFree)?,
+            },
+            virtual_memory,
+            largest_free_block: self
+                .optional_bytes(NISysCfgSystemProperty_NISysCfgSystemPropertyMemoryLargestBlock)?,
+        })
+    }
+
+    /// The usage of the primary disk of the system.
+    ///
+    /// # Example
+    /// ```
+    /// use ni_syscfg::{SessionConfig, UsageLimit};
+    ///
+    /// let session = SessionConfig::new().target("192.168.0.10").connect().unwrap();
+    ///
+    /// if session.disk().unwrap().exceeds(UsageLimit::MinFreeBytes(100 * 1024 * 1024)) {
+    ///   println!("Less than 100 MB of disk space left");
+    /// }
+    /// ```
+    pub fn disk(&self) -> Result<Usage> {
+        Ok(Usage {
+            total_bytes: self
+                .bytes(NISysCfgSystemProperty_NISysCfgSystemPropertyPrimaryDiskTotal)?,
+            free_bytes: self.bytes(NISysCfgSystemProperty_NISysCfgSystemPropertyPrimaryDiskFree)?,
+        })
+    }
+
+    /// The API reports sizes in bytes as doubles.
+    fn bytes(&self, id: NISysCfgSystemProperty) -> Result<u64> {
+        Ok(f64::read_system_parameter(self.handle(), id)?.max(0.0) as u64)
+    }
+
+    fn optional_bytes(&self, id: NISysCfgSystemProperty) -> Result<Option<u64>> {
+        optional_property(self.bytes(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEGABYTE: u64 = 1024 * 1024;
+
+    fn disk(total: u64, free: u64) -> Usage {
+        Usage {
+            total_bytes: total * MEGABYTE,
+            free_bytes: free * MEGABYTE,
+        }
+    }
+
+    #[test]
+    fn test_utilization() {
+        let usage = disk(1000, 250);
+        assert_eq!(usage.used_bytes(), 750 * MEGABYTE);
+        assert_eq!(usage.utilization(), 75.0);
+        assert_eq!(disk(0, 0).utilization(), 0.0);
+    }
+
+    #[test]
+    fn test_limits() {
+        let usage = disk(1000, 50);
+        assert!(usage.exceeds(UsageLimit::MaxUtilization(90.0)));
+        assert!(!usage.exceeds(UsageLimit::MaxUtilization(95.0)));
+        assert!(usage.exceeds(UsageLimit::MinFreeBytes(100 * MEGABYTE)));
+        assert!(!usage.exceeds(UsageLimit::MinFreeBytes(50 * MEGABYTE)));
+    }
+}
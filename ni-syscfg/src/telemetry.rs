@@ -0,0 +1,427 @@
+//! Sampling system health in the background.
+//!
+//! A [TelemetryPoller] opens a [Session] on its own thread and takes a [TelemetrySample]
+//! every interval, passing it to a callback or an [mpsc] channel.
+
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::error::{NiSystemConfigurationError, Result};
+use crate::resources::{CpuLoad, PowerSupply, SensorReadings};
+use crate::system::{MemoryUsage, Usage};
+use crate::Session;
+
+/// A group of values which a [TelemetryPoller] can sample.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// The load on each logical processor.
+    Cpu,
+    Memory,
+    Disk,
+    /// The sensors on each chassis and controller.
+    Sensors,
+    /// The power supplies of each PXI chassis.
+    PowerSupplies,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 5] = [
+        Metric::Cpu,
+        Metric::Memory,
+        Metric::Disk,
+        Metric::Sensors,
+        Metric::PowerSupplies,
+    ];
+}
+
+/// Somewhere telemetry can be read from.
+///
+/// This is implemented for [Session] and can be implemented for other sources, such as a
+/// stub in tests.
+pub trait TelemetrySource {
+    fn cpu_loads(&self) -> Result<Vec<CpuLoad>>;
+    fn memory(&self) -> Result<MemoryUsage>;
+    fn disk(&self) -> Result<Usage>;
+    /// The sensor readings of each resource, paired with the resource name.
+    fn sensor_readings(&self) -> Result<Vec<(String, SensorReadings)>>;
+    /// The power supplies of each chassis, paired with the chassis name.
+    fn power_supplies(&self) -> Result<Vec<(String, Vec<PowerSupply>)>>;
+}
+
+impl TelemetrySource for Session {
+    fn cpu_loads(&self) -> Result<Vec<CpuLoad>> {
+        Session::cpu_loads(self)
+    }
+
+    fn memory(&self) -> Result<MemoryUsage> {
+        Session::memory(self)
+    }
+
+    fn disk(&self) -> Result<Usage> {
+        Session::disk(self)
+    }
+
+    fn sensor_readings(&self) -> Result<Vec<(String, SensorReadings)>> {
+        Session::sensor_readings(self)
+    }
+
+    fn power_supplies(&self) -> Result<Vec<(String, Vec<PowerSupply>)>> {
+        Session::power_supplies(self)
+    }
+}
+
+/// The metrics read at one point in time.
+///
+/// Metrics which weren't requested are [None]. Metrics which failed to read are also [None]
+/// with the error in `errors`.
+#[derive(Debug)]
+pub struct TelemetrySample {
+    pub timestamp: SystemTime,
+    pub cpu: Option<Vec<CpuLoad>>,
+    pub memory: Option<MemoryUsage>,
+    pub disk: Option<Usage>,
+    pub sensors: Option<Vec<(String, SensorReadings)>>,
+    pub power_supplies: Option<Vec<(String, Vec<PowerSupply>)>>,
+    pub errors: Vec<(Metric, NiSystemConfigurationError)>,
+}
+
+impl TelemetrySample {
+    /// Read the `metrics` from the `source` now.
+    pub fn collect(source: &impl TelemetrySource, metrics: &[Metric]) -> Self {
+        let mut sample = TelemetrySample {
+            timestamp: SystemTime::now(),
+            cpu: None,
+            memory: None,
+            disk: None,
+            sensors: None,
+            power_supplies: None,
+            errors: Vec::new(),
+        };
+        for &metric in metrics {
+            let result = match metric {
+                Metric::Cpu => source.cpu_loads().map(|value| sample.cpu = Some(value)),
+                Metric::Memory => source.memory().map(|value| sample.memory = Some(value)),
+                Metric::Disk => source.disk().map(|value| sample.disk = Some(value)),
+                Metric::Sensors => source
+                    .sensor_readings()
+                    .map(|value| sample.sensors = Some(value)),
+                Metric::PowerSupplies => source
+                    .power_supplies()
+                    .map(|value| sample.power_supplies = Some(value)),
+            };
+            if let Err(error) = result {
+                sample.errors.push((metric, error));
+            }
+        }
+        sample
+    }
+
+    /// Returns true if every requested metric was read.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// The settings for a [TelemetryPoller].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    interval: Duration,
+    metrics: Vec<Metric>,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(10),
+            metrics: Metric::ALL.to_vec(),
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// The shortest interval a [TelemetryPoller] will sample at.
+    pub const MIN_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Sample every metric every 10 seconds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how often to take a sample.
+    ///
+    /// Intervals shorter than [TelemetryConfig::MIN_INTERVAL] are raised to it so the poller never spins.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval.max(Self::MIN_INTERVAL);
+        self
+    }
+
+    /// Set which metrics to read in each sample.
+    pub fn metrics(mut self, metrics: &[Metric]) -> Self {
+        self.metrics = metrics.to_vec();
+        self
+    }
+}
+
+/// Samples telemetry on a background thread until it is stopped or dropped.
+///
+/// The first sample is taken as soon as the poller starts. A metric which fails to read is
+/// reported in [TelemetrySample::errors] and the poller carries on.
+///
+/// The session is opened on the poller thread so it is never shared between threads. If it
+/// can't be opened no samples are taken and the error is returned by [TelemetryPoller::stop].
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use ni_syscfg::{Metric, SessionConfig, TelemetryConfig, TelemetryPoller};
+///
+/// let config = TelemetryConfig::new()
+///   .interval(Duration::from_secs(5))
+///   .metrics(&[Metric::Cpu, Metric::Disk]);
+///
+/// let connect = || SessionConfig::new().target("192.168.0.10").connect();
+/// let (poller, samples) = TelemetryPoller::channel(connect, config);
+/// for sample in samples.iter().take(3) {
+///   println!("{:?}", sample.disk);
+/// }
+/// poller.stop().unwrap();
+/// ```
+pub struct TelemetryPoller {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<Result<()>>>,
+}
+
+impl TelemetryPoller {
+    /// Open a session with `connect` on the poller thread and poll it, passing each sample
+    /// to `callback` on that thread.
+    pub fn start(
+        connect: impl FnOnce() -> Result<Session> + Send + 'static,
+        config: TelemetryConfig,
+        mut callback: impl FnMut(TelemetrySample) + Send + 'static,
+    ) -> Self {
+        Self::spawn(connect, config, move |sample| {
+            callback(sample);
+            true
+        })
+    }
+
+    /// Open a session with `connect` on the poller thread and poll it, sending each sample
+    /// to the returned receiver.
+    ///
+    /// Polling stops when the receiver is dropped. The receiver is closed without any samples
+    /// if the session can't be opened.
+    pub fn channel(
+        connect: impl FnOnce() -> Result<Session> + Send + 'static,
+        config: TelemetryConfig,
+    ) -> (Self, mpsc::Receiver<TelemetrySample>) {
+        Self::channel_with_source(connect, config)
+    }
+
+    fn channel_with_source<S: TelemetrySource>(
+        open: impl FnOnce() -> Result<S> + Send + 'static,
+        config: TelemetryConfig,
+    ) -> (Self, mpsc::Receiver<TelemetrySample>) {
+        let (sender, receiver) = mpsc::channel();
+        let poller = Self::spawn(open, config, move |sample| sender.send(sample).is_ok());
+        (poller, receiver)
+    }
+
+    /// Start polling any [TelemetrySource], passing each sample to `callback`.
+    pub fn start_with_source<S: TelemetrySource + Send + 'static>(
+        source: S,
+        config: TelemetryConfig,
+        mut callback: impl FnMut(TelemetrySample) + Send + 'static,
+    ) -> Self {
+        Self::spawn(
+            move || Ok(source),
+            config,
+            move |sample| {
+                callback(sample);
+                true
+            },
+        )
+    }
+
+    /// Open the source with `open` on a new thread and poll it until stopped or until
+    /// `callback` returns false.
+    fn spawn<S: TelemetrySource>(
+        open: impl FnOnce() -> Result<S> + Send + 'static,
+        config: TelemetryConfig,
+        mut callback: impl FnMut(TelemetrySample) -> bool + Send + 'static,
+    ) -> Self {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = thread::spawn(move || {
+            let source = open()?;
+            let mut next = Instant::now();
+            loop {
+                if !callback(TelemetrySample::collect(&source, &config.metrics)) {
+                    break;
+                }
+
+                next = next_sample_time(next, config.interval, Instant::now());
+                let wait = next.saturating_duration_since(Instant::now());
+                match stopped.recv_timeout(wait) {
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            Ok(())
+        });
+        Self {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    /// Stop polling and wait for the thread to finish any sample in progress.
+    ///
+    /// Returns the error if the session couldn't be opened.
+    pub fn stop(mut self) -> Result<()> {
+        self.shutdown()
+    }
+
+    fn shutdown(&mut self) -> Result<()> {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        match self.thread.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            // A panic in the callback has already been reported on the thread.
+            Some(Err(_)) | None => Ok(()),
+        }
+    }
+}
+
+impl Drop for TelemetryPoller {
+    fn drop(&mut self) {
+        let _ = self.shutdown();
+    }
+}
+
+/// When to take the sample after the one scheduled for `previous`.
+///
+/// Samples are scheduled from the last deadline so polling doesn't drift. If a sample took
+/// longer than the interval, the schedule restarts from `now` rather than sampling back to
+/// back to catch up.
+fn next_sample_time(previous: Instant, interval: Duration, now: Instant) -> Instant {
+    let next = previous + interval;
+    if next <= now {
+        now + interval
+    } else {
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::NiSysCfgApiStatus;
+    use crate::test_support::StubSource;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn test_collect_reports_failed_metrics() {
+        let sample = TelemetrySample::collect(&StubSource::default(), &Metric::ALL);
+
+        assert_eq!(sample.cpu.as_ref().unwrap()[0].total_load, 35);
+        assert_eq!(sample.memory.unwrap().physical.free_bytes, 4096);
+        assert!(sample.disk.is_none());
+        assert_eq!(sample.errors.len(), 1);
+        assert_eq!(sample.errors[0].0, Metric::Disk);
+        assert!(!sample.is_complete());
+    }
+
+    #[test]
+    fn test_collect_only_requested_metrics() {
+        let sample = TelemetrySample::collect(&StubSource::default(), &[Metric::Memory]);
+        assert!(sample.cpu.is_none());
+        assert!(sample.memory.is_some());
+        assert!(sample.is_complete());
+    }
+
+    #[test]
+    fn test_poller_keeps_sampling_after_errors_and_stops() {
+        let source = StubSource::default();
+        let reads = source.reads.clone();
+        let (sender, samples) = mpsc::channel();
+        let config = TelemetryConfig::new()
+            .interval(Duration::from_millis(1))
+            .metrics(&[Metric::Cpu, Metric::Disk]);
+
+        let poller = TelemetryPoller::start_with_source(source, config, move |sample| {
+            let _ = sender.send(sample);
+        });
+        let received: Vec<_> = samples.iter().take(3).collect();
+        poller.stop().unwrap();
+
+        assert!(received.iter().all(|sample| sample.errors.len() == 1));
+        let reads_after_stop = reads.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(reads.load(Ordering::SeqCst), reads_after_stop);
+    }
+
+    #[test]
+    fn test_next_sample_time() {
+        let start = Instant::now();
+        let interval = Duration::from_secs(10);
+
+        let on_time = next_sample_time(start, interval, start + Duration::from_secs(2));
+        assert_eq!(on_time, start + interval);
+
+        let late = start + Duration::from_secs(25);
+        assert_eq!(next_sample_time(start, interval, late), late + interval);
+    }
+
+    #[test]
+    fn test_channel_poller_stops_when_receiver_dropped() {
+        let config = TelemetryConfig::new()
+            .interval(Duration::from_millis(1))
+            .metrics(&[Metric::Cpu]);
+
+        let (poller, samples) =
+            TelemetryPoller::channel_with_source(|| Ok(StubSource::default()), config);
+        samples.recv().unwrap();
+        drop(samples);
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !poller.thread.as_ref().unwrap().is_finished() {
+            assert!(Instant::now() < deadline, "poller kept running");
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_stop_does_not_wait_for_interval() {
+        let config = TelemetryConfig::new().interval(Duration::from_secs(3600));
+        let poller = TelemetryPoller::start_with_source(StubSource::default(), config, |_| {});
+
+        let started = Instant::now();
+        poller.stop().unwrap();
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_connection_error_is_returned_by_stop() {
+        let connect = || {
+            Err(NiSystemConfigurationError::ApiError(
+                NiSysCfgApiStatus::UriTargetDoesNotExist,
+            ))
+        };
+        let (poller, samples) = TelemetryPoller::channel(connect, TelemetryConfig::new());
+
+        assert!(samples.recv().is_err());
+        assert!(matches!(
+            poller.stop(),
+            Err(NiSystemConfigurationError::ApiError(
+                NiSysCfgApiStatus::UriTargetDoesNotExist
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_zero_interval_is_clamped() {
+        let config = TelemetryConfig::new().interval(Duration::ZERO);
+        assert_eq!(config.interval, TelemetryConfig::MIN_INTERVAL);
+    }
+}
@@ -0,0 +1,82 @@
+//! Fixtures shared between the unit tests of different modules.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::error::{NiSysCfgApiStatus, NiSystemConfigurationError, Result};
+use crate::resources::{
+    CpuLoad, PowerSupply, PowerSupplyState, SensorKind, SensorReading, SensorReadings,
+};
+use crate::system::{MemoryUsage, Usage};
+use crate::telemetry::TelemetrySource;
+
+/// A sensor reading with no nominal value or limits.
+pub(crate) fn reading(kind: SensorKind, name: &str, value: f64) -> SensorReading {
+    SensorReading {
+        kind,
+        name: name.to_string(),
+        value,
+        nominal: None,
+        lower_critical: None,
+        upper_critical: None,
+    }
+}
+
+/// Stands in for a session on a PXI system with one chassis.
+///
+/// The disk can't be read, and each read of the CPU loads is counted in `reads`.
+#[derive(Default, Clone)]
+pub(crate) struct StubSource {
+    pub reads: Arc<AtomicUsize>,
+}
+
+impl TelemetrySource for StubSource {
+    fn cpu_loads(&self) -> Result<Vec<CpuLoad>> {
+        self.reads.fetch_add(1, Ordering::SeqCst);
+        Ok(vec![CpuLoad {
+            processor: 0,
+            total_load: 35,
+            interrupt_load: 5,
+            speed_mhz: 2200,
+        }])
+    }
+
+    fn memory(&self) -> Result<MemoryUsage> {
+        Ok(MemoryUsage {
+            physical: Usage {
+                total_bytes: 8192,
+                free_bytes: 4096,
+            },
+            virtual_memory: None,
+            largest_free_block: Some(1024),
+        })
+    }
+
+    fn disk(&self) -> Result<Usage> {
+        Err(NiSystemConfigurationError::ApiError(
+            NiSysCfgApiStatus::PropDoesNotExist,
+        ))
+    }
+
+    fn sensor_readings(&self) -> Result<Vec<(String, SensorReadings)>> {
+        Ok(vec![(
+            "PXI1Chassis1".to_string(),
+            SensorReadings {
+                temperature: vec![reading(SensorKind::Temperature, "Intake \"A\"", 31.5)],
+                fan: vec![reading(SensorKind::Fan, "Fan 1", 1800.0)],
+                voltage: vec![reading(SensorKind::Voltage, "3.3V", 3.31)],
+                ..Default::default()
+            },
+        )])
+    }
+
+    fn power_supplies(&self) -> Result<Vec<(String, Vec<PowerSupply>)>> {
+        Ok(vec![(
+            "PXI1Chassis1".to_string(),
+            vec![PowerSupply::from_state_code(
+                "PS1".to_string(),
+                PowerSupplyState::AlertFan as i32,
+            )],
+        )])
+    }
+}
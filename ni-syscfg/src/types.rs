@@ -3,6 +3,9 @@
 
 use ni_syscfg_sys::{NISysCfgBool_NISysCfgBoolFalse, NISysCfgBool_NISysCfgBoolTrue};
 
+/// Sizes the API reports in megabytes are converted to bytes with this.
+pub(crate) const BYTES_PER_MEGABYTE: u64 = 1024 * 1024;
+
 /// Wraps the constant values for true and false elements.
 #[repr(i32)]
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]